@@ -9,7 +9,7 @@ use crate::animation::SaveBackupSystems;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(Backup<Transform>, Backup<BoxShadow>)>();
+    app.configure::<(Backup<Transform>, Backup<BoxShadow>, SpriteColorBackup)>();
 
     // Restore `GlobalTransform` after restoring `Transform`.
     app.add_systems(
@@ -58,3 +58,33 @@ fn save_to_backup<C: Component<Mutability = Mutable> + Clone>(
         backup.0 = Some(target.clone());
     }
 }
+
+/// Saves the pre-animation [`Sprite::color`] to be restored next frame.
+///
+/// Unlike `Backup<Sprite>`, this leaves changes to the rest of the sprite intact,
+/// such as flipping or the current animation frame.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct SpriteColorBackup(Option<Color>);
+
+impl Configure for SpriteColorBackup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(First, restore_sprite_color);
+        app.add_systems(PostUpdate, save_sprite_color.in_set(SaveBackupSystems));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn restore_sprite_color(mut backup_query: Query<(&mut SpriteColorBackup, &mut Sprite)>) {
+    for (mut backup, mut sprite) in &mut backup_query {
+        sprite.color = c!(backup.0.take());
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn save_sprite_color(mut backup_query: Query<(&mut SpriteColorBackup, &Sprite)>) {
+    for (mut backup, sprite) in &mut backup_query {
+        backup.0 = Some(sprite.color);
+    }
+}
@@ -1,19 +1,31 @@
 use bevy::audio::AudioPlugin;
+use bevy::audio::SpatialScale;
 
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<AudioSettings>();
+    app.configure::<(AudioSettings, AudioDuck)>();
 
-    app.add_plugins(AudioPlugin::default());
+    app.add_plugins(AudioPlugin {
+        default_spatial_scale: SpatialScale::new_2d(SPATIAL_SCALE),
+        ..default()
+    });
 }
 
-#[derive(Resource, Reflect, Clone, Debug)]
+/// Spatial audio units per world unit.
+const SPATIAL_SCALE: f32 = 1.0 / 100.0;
+
+/// The distance between the ears of a [`SpatialListener`] in world units.
+pub const EAR_GAP: f32 = 64.0;
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct AudioSettings {
     pub master_volume: f32,
     pub music_volume: f32,
     pub ui_volume: f32,
+    pub sfx_volume: f32,
 }
 
 impl Default for AudioSettings {
@@ -22,6 +34,7 @@ impl Default for AudioSettings {
             master_volume: 0.5,
             music_volume: 0.5,
             ui_volume: 0.5,
+            sfx_volume: 0.5,
         }
     }
 }
@@ -30,11 +43,11 @@ impl Configure for AudioSettings {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
         app.init_resource::<Self>();
-        app.configure::<(IsMusicAudio, IsUiAudio)>();
+        app.configure::<(IsMusicAudio, IsUiAudio, IsSfxAudio, IgnoreDuck, AudioFade)>();
         app.add_systems(
             Update,
             apply_audio_settings
-                .run_if(resource_changed::<Self>)
+                .run_if(resource_changed::<Self>.or(resource_changed::<AudioDuck>))
                 .in_set(UpdateSystems::Update),
         );
     }
@@ -48,22 +61,69 @@ impl AudioSettings {
     pub fn ui_volume(&self) -> Volume {
         Volume::Linear(self.master_volume * self.ui_volume)
     }
+
+    pub fn sfx_volume(&self) -> Volume {
+        Volume::Linear(self.master_volume * self.sfx_volume)
+    }
+}
+
+/// Volume multipliers for ducking music and SFX under more important sounds.
+#[derive(Resource, Reflect, Copy, Clone, PartialEq, Debug)]
+#[reflect(Resource)]
+pub struct AudioDuck {
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Configure for AudioDuck {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+impl Default for AudioDuck {
+    fn default() -> Self {
+        Self {
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+/// An SFX entity that is not affected by [`AudioDuck`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct IgnoreDuck;
+
+impl Configure for IgnoreDuck {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
 }
 
 #[cfg_attr(feature = "native_dev", hot)]
 fn apply_audio_settings(
     audio_settings: Res<AudioSettings>,
-    music_audio_query: Query<Entity, With<IsMusicAudio>>,
-    ui_audio_query: Query<Entity, With<IsUiAudio>>,
-    mut volume_query: Query<(Option<&mut PlaybackSettings>, Option<&mut AudioSink>)>,
+    duck: Res<AudioDuck>,
+    music_audio_query: Query<Entity, (With<IsMusicAudio>, Without<AudioFade>)>,
+    ui_audio_query: Query<Entity, (With<IsUiAudio>, Without<AudioFade>)>,
+    sfx_audio_query: Query<(Entity, Has<IgnoreDuck>), (With<IsSfxAudio>, Without<AudioFade>)>,
+    mut volume_query: Query<(
+        Option<&mut PlaybackSettings>,
+        Option<&mut AudioSink>,
+        Option<&mut SpatialAudioSink>,
+    )>,
 ) {
     // Apply music volume.
-    let volume = audio_settings.music_volume();
+    let volume = Volume::Linear(audio_settings.music_volume().to_linear() * duck.music);
     for entity in &music_audio_query {
-        let (playback, sink) = c!(volume_query.get_mut(entity));
+        let (playback, sink, spatial_sink) = c!(volume_query.get_mut(entity));
 
         if let Some(mut sink) = sink {
             sink.set_volume(volume);
+        } else if let Some(mut sink) = spatial_sink {
+            sink.set_volume(volume);
         } else if let Some(mut playback) = playback {
             playback.volume = volume;
         }
@@ -72,10 +132,115 @@ fn apply_audio_settings(
     // Apply UI volume.
     let volume = audio_settings.ui_volume();
     for entity in &ui_audio_query {
-        let (playback, sink) = c!(volume_query.get_mut(entity));
+        let (playback, sink, spatial_sink) = c!(volume_query.get_mut(entity));
 
         if let Some(mut sink) = sink {
             sink.set_volume(volume);
+        } else if let Some(mut sink) = spatial_sink {
+            sink.set_volume(volume);
+        } else if let Some(mut playback) = playback {
+            playback.volume = volume;
+        }
+    }
+
+    // Apply SFX volume.
+    let base = audio_settings.sfx_volume().to_linear();
+    for (entity, ignore_duck) in &sfx_audio_query {
+        let (playback, sink, spatial_sink) = c!(volume_query.get_mut(entity));
+        let volume = Volume::Linear(if ignore_duck { base } else { base * duck.sfx });
+
+        if let Some(mut sink) = sink {
+            sink.set_volume(volume);
+        } else if let Some(mut sink) = spatial_sink {
+            sink.set_volume(volume);
+        } else if let Some(mut playback) = playback {
+            playback.volume = volume;
+        }
+    }
+}
+
+/// Fade the volume of an audio entity, as a multiplier on its category volume.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct AudioFade {
+    pub volume: f32,
+    pub target: f32,
+    /// The change in volume per second.
+    pub rate: f32,
+    /// Whether to despawn the entity once it has faded out.
+    pub despawn: bool,
+}
+
+impl Configure for AudioFade {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            apply_audio_fade
+                .in_set(UpdateSystems::Update)
+                .after(apply_audio_settings),
+        );
+    }
+}
+
+impl AudioFade {
+    pub fn fade_in(secs: f32) -> Self {
+        Self {
+            volume: 0.0,
+            target: 1.0,
+            rate: secs.max(f32::EPSILON).recip(),
+            despawn: false,
+        }
+    }
+
+    pub fn fade_out(&mut self) {
+        self.target = 0.0;
+        self.despawn = true;
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_audio_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    audio_settings: Res<AudioSettings>,
+    duck: Res<AudioDuck>,
+    mut fade_query: Query<(
+        Entity,
+        &mut AudioFade,
+        Has<IsMusicAudio>,
+        Has<IsUiAudio>,
+        Has<IgnoreDuck>,
+        Option<&mut PlaybackSettings>,
+        Option<&mut AudioSink>,
+        Option<&mut SpatialAudioSink>,
+    )>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut fade, is_music, is_ui, ignore_duck, playback, sink, spatial_sink) in
+        &mut fade_query
+    {
+        let step = fade.rate * dt;
+        fade.volume += (fade.target - fade.volume).clamp(-step, step);
+        if fade.despawn && fade.volume <= f32::EPSILON {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let base = if is_music {
+            audio_settings.music_volume().to_linear() * duck.music
+        } else if is_ui {
+            audio_settings.ui_volume().to_linear()
+        } else if ignore_duck {
+            audio_settings.sfx_volume().to_linear()
+        } else {
+            audio_settings.sfx_volume().to_linear() * duck.sfx
+        };
+        let volume = Volume::Linear(base * fade.volume);
+        if let Some(mut sink) = sink {
+            sink.set_volume(volume);
+        } else if let Some(mut sink) = spatial_sink {
+            sink.set_volume(volume);
         } else if let Some(mut playback) = playback {
             playback.volume = volume;
         }
@@ -92,12 +257,19 @@ impl Configure for IsMusicAudio {
     }
 }
 
-pub fn music_audio(audio_settings: &AudioSettings, handle: Handle<AudioSource>) -> impl Bundle {
+/// Music that starts at the volume of `fade`, so it doesn't play a frame at full volume.
+pub fn music_audio(
+    audio_settings: &AudioSettings,
+    handle: Handle<AudioSource>,
+    fade: AudioFade,
+) -> impl Bundle {
+    let volume = Volume::Linear(audio_settings.music_volume().to_linear() * fade.volume);
     (
         Name::new("MusicAudio"),
         AudioPlayer(handle),
-        PlaybackSettings::LOOP.with_volume(audio_settings.music_volume()),
+        PlaybackSettings::LOOP.with_volume(volume),
         IsMusicAudio,
+        fade,
     )
 }
 
@@ -121,3 +293,37 @@ pub fn ui_audio(audio_settings: &AudioSettings, handle: Handle<AudioSource>) ->
         IsUiAudio,
     )
 }
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct IsSfxAudio;
+
+impl Configure for IsSfxAudio {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// A sound effect, played relative to the [`SpatialListener`] if it has a position.
+///
+/// The volume is a multiplier on the SFX volume, e.g. for ducking.
+pub fn sfx_audio(
+    audio_settings: &AudioSettings,
+    handle: Handle<AudioSource>,
+    volume: f32,
+    speed: f32,
+    position: Option<Vec2>,
+) -> impl Bundle {
+    (
+        Name::new("SfxSample"),
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(
+                audio_settings.sfx_volume().to_linear() * volume,
+            ))
+            .with_speed(speed)
+            .with_spatial(position.is_some()),
+        Transform::from_translation(position.unwrap_or_default().extend(0.0)),
+        IsSfxAudio,
+    )
+}
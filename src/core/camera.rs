@@ -1,7 +1,24 @@
+use std::f32::consts::TAU;
+
+use crate::animation::offset::Offset;
+use crate::core::audio::EAR_GAP;
+use crate::core::window::VideoSettings;
+use crate::core::window::WindowRoot;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(CameraRoot, SmoothFollow, AbsoluteScale)>();
+    app.configure::<(
+        CameraRoot,
+        SmoothFollow,
+        CameraBounds,
+        CameraLookAhead,
+        CameraZoom,
+        CameraGroup,
+        CameraShake,
+        OnCameraShake,
+        CameraSettings,
+        AbsoluteScale,
+    )>();
 }
 
 #[derive(Resource, Reflect)]
@@ -33,6 +50,12 @@ impl FromWorld for CameraRoot {
                         target: Entity::PLACEHOLDER,
                         rate: Vec2::splat(100.0),
                     },
+                    CameraBounds::default(),
+                    CameraLookAhead::default(),
+                    CameraZoom::default(),
+                    CameraGroup::default(),
+                    CameraShake::default(),
+                    SpatialListener::new(EAR_GAP),
                     IsDefaultUiCamera,
                 ))
                 .id(),
@@ -60,18 +83,369 @@ impl Configure for SmoothFollow {
 #[cfg_attr(feature = "native_dev", hot)]
 fn apply_smooth_follow(
     time: Res<Time>,
-    mut follow_query: Query<(&mut Transform, &SmoothFollow)>,
+    mut follow_query: Query<(
+        &mut Transform,
+        &SmoothFollow,
+        Option<&mut CameraLookAhead>,
+        Option<&CameraBounds>,
+        Option<&mut CameraGroup>,
+        Option<&Projection>,
+    )>,
     target_query: Query<&GlobalTransform, Without<SmoothFollow>>,
 ) {
     let dt = time.delta_secs();
-    for (mut transform, follow) in &mut follow_query {
-        let target_pos = cq!(target_query.get(follow.target)).translation().xy();
+    for (mut transform, follow, look_ahead, bounds, group, projection) in &mut follow_query {
+        let mut target_pos = cq!(target_query.get(follow.target)).translation().xy();
+        if let (Some(mut group), Some(Projection::Orthographic(projection))) = (group, projection) {
+            let points = group
+                .targets
+                .iter()
+                .filter_map(|&entity| target_query.get(entity).ok())
+                .map(|gt| gt.translation().xy())
+                .collect::<Vec<_>>();
+            let base_size = projection.area.size() / projection.scale;
+            (target_pos, group.required_zoom) = group.frame(target_pos, &points, base_size);
+        }
+        if let Some(mut look_ahead) = look_ahead {
+            let lead = look_ahead.direction * look_ahead.distance;
+            let t = (look_ahead.rate * dt).clamp(0.0, 1.0);
+            look_ahead.current = look_ahead.current.lerp(lead, t);
+            target_pos += look_ahead.current;
+        }
+        if let (Some(CameraBounds(Some(bounds))), Some(Projection::Orthographic(projection))) =
+            (bounds, projection)
+        {
+            target_pos = clamp_view(target_pos, projection.area.half_size(), *bounds);
+        }
+
         let mut pos = transform.translation.xy();
         pos += (target_pos - pos) * (follow.rate * dt).clamp(Vec2::ZERO, Vec2::ONE);
         transform.translation = pos.extend(transform.translation.z);
     }
 }
 
+/// Clamp a camera position so that its view stays inside the bounds.
+///
+/// Centers the view on any axis where the bounds are smaller than the view.
+fn clamp_view(pos: Vec2, half_size: Vec2, bounds: Rect) -> Vec2 {
+    let min = bounds.min + half_size;
+    let max = bounds.max - half_size;
+    let center = bounds.center();
+    vec2(
+        if min.x <= max.x {
+            pos.x.clamp(min.x, max.x)
+        } else {
+            center.x
+        },
+        if min.y <= max.y {
+            pos.y.clamp(min.y, max.y)
+        } else {
+            center.y
+        },
+    )
+}
+
+/// Frame several targets with a [`SmoothFollow`] camera.
+///
+/// The camera centers on the bounding box of the targets and zooms out to keep them all
+/// on screen, up to `max_zoom`. Has no effect with fewer than two targets.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraGroup {
+    pub targets: Vec<Entity>,
+    /// Extra space to keep around the bounding box, in world units.
+    pub padding: Vec2,
+    /// The furthest the camera will zoom out to frame the targets.
+    pub max_zoom: f32,
+    /// What to do when the targets don't fit at `max_zoom`.
+    pub fallback: CameraGroupFallback,
+    required_zoom: f32,
+}
+
+impl Configure for CameraGroup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for CameraGroup {
+    fn default() -> Self {
+        Self {
+            targets: vec![],
+            padding: vec2(64.0, 48.0),
+            max_zoom: 2.0,
+            fallback: CameraGroupFallback::default(),
+            required_zoom: 0.0,
+        }
+    }
+}
+
+impl CameraGroup {
+    /// Compute the camera position and the zoom required to frame every point.
+    fn frame(&self, primary: Vec2, points: &[Vec2], base_size: Vec2) -> (Vec2, f32) {
+        let (&first, rest) = match points.split_first() {
+            Some(split) if !split.1.is_empty() => split,
+            _ => return (primary, 0.0),
+        };
+        let rect = rest
+            .iter()
+            .fold(Rect::from_center_size(first, Vec2::ZERO), |rect, &p| {
+                rect.union_point(p)
+            });
+        let zoom = ((rect.size() + 2.0 * self.padding) / base_size).max_element();
+        if zoom <= self.max_zoom {
+            return (rect.center(), zoom);
+        }
+
+        match self.fallback {
+            CameraGroupFallback::FollowPrimary => (primary, self.max_zoom),
+            CameraGroupFallback::Center => (rect.center(), self.max_zoom),
+        }
+    }
+}
+
+/// What a [`CameraGroup`] does when its targets are too far apart to frame.
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub enum CameraGroupFallback {
+    /// Follow the [`SmoothFollow`] target and let the others go off screen.
+    #[default]
+    FollowPrimary,
+    /// Stay centered on the group and let the outermost targets go off screen.
+    Center,
+}
+
+/// Keep the view of a [`SmoothFollow`] camera inside a world-space rectangle.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct CameraBounds(pub Option<Rect>);
+
+impl Configure for CameraBounds {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Lead the target of a [`SmoothFollow`] camera in a direction.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraLookAhead {
+    /// The direction to lead in, with a length between 0 and 1.
+    pub direction: Vec2,
+    /// The distance to lead by in world units.
+    pub distance: f32,
+    /// How quickly the lead catches up to its new value.
+    pub rate: f32,
+    current: Vec2,
+}
+
+impl Configure for CameraLookAhead {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for CameraLookAhead {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::ZERO,
+            distance: 0.0,
+            rate: 4.0,
+            current: Vec2::ZERO,
+        }
+    }
+}
+
+/// Smoothly zoom an orthographic camera.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraZoom {
+    /// The projection scale to zoom towards. Larger values zoom out.
+    pub target: f32,
+    /// How quickly the zoom catches up to its target.
+    pub rate: f32,
+    /// The current projection scale, before pixel-perfect snapping.
+    pub current: f32,
+}
+
+impl Configure for CameraZoom {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, apply_camera_zoom.in_set(PausableSystems));
+    }
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            target: 1.0,
+            rate: 3.0,
+            current: 1.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_camera_zoom(
+    time: Res<Time>,
+    video_settings: Res<VideoSettings>,
+    window_root: Res<WindowRoot>,
+    window_query: Query<&Window>,
+    mut zoom_query: Query<(&mut Projection, &mut CameraZoom, Option<&CameraGroup>)>,
+) {
+    let dt = time.delta_secs();
+    let scale_factor = r!(window_query.get(window_root.primary)).scale_factor();
+    for (mut projection, mut zoom, group) in &mut zoom_query {
+        let Projection::Orthographic(projection) = projection.as_mut() else {
+            continue;
+        };
+        // Zoom out further if needed to frame a group.
+        let target = zoom
+            .target
+            .max(group.map_or(0.0, |group| group.required_zoom));
+        zoom.current += (target - zoom.current) * (zoom.rate * dt).clamp(0.0, 1.0);
+
+        projection.scale = if video_settings.pixel_perfect {
+            // Round the number of screen pixels per world pixel, zooming out.
+            let ratio = scale_factor / zoom.current;
+            let ratio = if ratio >= 1.0 {
+                ratio.floor()
+            } else {
+                (1.0 / ratio).ceil().recip()
+            };
+            scale_factor / ratio
+        } else {
+            zoom.current
+        };
+    }
+}
+
+/// Trauma-based screen shake.
+///
+/// The shake is written to [`Offset`], so it never affects the position
+/// computed by [`SmoothFollow`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(Offset)]
+pub struct CameraShake {
+    /// The current trauma, between 0 and 1.
+    pub trauma: f32,
+    /// The amount of trauma lost per second.
+    pub decay: f32,
+    /// The offset in world units at full trauma.
+    pub max_offset: Vec2,
+    /// The shake frequency in Hz.
+    pub frequency: f32,
+    elapsed: f32,
+}
+
+impl Configure for CameraShake {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            apply_camera_shake
+                .in_set(UpdateSystems::SyncLate)
+                .in_set(PausableSystems),
+        );
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: Vec2::splat(12.0),
+            frequency: 12.0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).clamp(0.0, 1.0);
+    }
+
+    pub fn reset(&mut self) {
+        self.trauma = 0.0;
+    }
+}
+
+/// Smooth pseudo-random noise in the range `[-1, 1]`.
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    0.5 * (t + seed).sin() + 0.3 * (2.3 * t + 1.7 * seed).sin() + 0.2 * (5.1 * t + 3.1 * seed).sin()
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_camera_shake(
+    time: Res<Time>,
+    camera_settings: Res<CameraSettings>,
+    mut shake_query: Query<(&mut CameraShake, &mut Offset)>,
+) {
+    let dt = time.delta_secs();
+    for (mut shake, mut offset) in &mut shake_query {
+        shake.trauma = (shake.trauma - shake.decay * dt).max(0.0);
+        shake.elapsed += dt;
+
+        let intensity = camera_settings.shake_intensity();
+        let t = TAU * shake.frequency * shake.elapsed;
+        let noise = vec2(shake_noise(t, 0.0), shake_noise(t, 10.0));
+        offset.0 = shake.max_offset * noise * shake.trauma.powi(2) * intensity;
+    }
+}
+
+/// An event that adds trauma to every [`CameraShake`].
+#[derive(Event, Reflect, Debug)]
+pub struct OnCameraShake(pub f32);
+
+impl Configure for OnCameraShake {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(add_camera_trauma);
+    }
+}
+
+fn add_camera_trauma(trigger: Trigger<OnCameraShake>, mut shake_query: Query<&mut CameraShake>) {
+    for mut shake in &mut shake_query {
+        shake.add_trauma(trigger.0);
+    }
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct CameraSettings {
+    pub shake_enabled: bool,
+    pub shake_intensity: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            shake_enabled: true,
+            shake_intensity: 1.0,
+        }
+    }
+}
+
+impl Configure for CameraSettings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+impl CameraSettings {
+    pub fn shake_intensity(&self) -> f32 {
+        if self.shake_enabled {
+            self.shake_intensity
+        } else {
+            0.0
+        }
+    }
+}
+
 // TODO: Workaround for <https://github.com/bevyengine/bevy/issues/1890>.
 /// Camera zoom-independent scale.
 #[derive(Component, Reflect)]
@@ -1,6 +1,8 @@
 use bevy::window::ExitCondition;
+use bevy::window::MonitorSelection;
 use bevy::window::PresentMode;
 use bevy::window::PrimaryWindow;
+use bevy::window::VideoModeSelection;
 use bevy::window::WindowMode;
 
 use crate::prelude::*;
@@ -17,7 +19,12 @@ pub(super) fn plugin(app: &mut App) {
         ..default()
     });
 
-    app.configure::<(WindowRoot, ConfigHandle<WindowConfig>, WindowReady)>();
+    app.configure::<(
+        WindowRoot,
+        ConfigHandle<WindowConfig>,
+        WindowReady,
+        VideoSettings,
+    )>();
 }
 
 #[derive(Resource, Reflect)]
@@ -48,8 +55,6 @@ impl FromWorld for WindowRoot {
 #[serde(deny_unknown_fields)]
 pub struct WindowConfig {
     pub title: String,
-    pub window_mode: WindowMode,
-    pub present_mode: PresentMode,
 }
 
 impl Config for WindowConfig {
@@ -61,8 +66,6 @@ impl Config for WindowConfig {
         let window_root = r!(world.get_resource::<WindowRoot>());
         let mut window = r!(world.get_mut::<Window>(window_root.primary));
         window.title.clone_from(&self.title);
-        window.mode = self.window_mode;
-        window.present_mode = self.present_mode;
     }
 }
 
@@ -83,3 +86,78 @@ impl Configure for WindowReady {
 fn show_window(window_root: Res<WindowRoot>, mut window_query: Query<&mut Window>) {
     r!(window_query.get_mut(window_root.primary)).visible = true;
 }
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub window_mode: VideoWindowMode,
+    pub vsync: bool,
+    /// Snap the camera zoom so that world pixels map to a whole number of screen pixels.
+    pub pixel_perfect: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: VideoWindowMode::Windowed,
+            vsync: true,
+            pixel_perfect: false,
+        }
+    }
+}
+
+impl Configure for VideoSettings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            apply_video_settings
+                .run_if(resource_changed::<Self>)
+                .in_set(UpdateSystems::Update),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_video_settings(
+    video_settings: Res<VideoSettings>,
+    window_root: Res<WindowRoot>,
+    mut window_query: Query<&mut Window>,
+) {
+    let mut window = r!(window_query.get_mut(window_root.primary));
+    let mode = video_settings.window_mode.into();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    let present_mode = if video_settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum VideoWindowMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl From<VideoWindowMode> for WindowMode {
+    fn from(value: VideoWindowMode) -> Self {
+        match value {
+            VideoWindowMode::Windowed => WindowMode::Windowed,
+            VideoWindowMode::Borderless => {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            },
+            VideoWindowMode::Fullscreen => {
+                WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+            },
+        }
+    }
+}
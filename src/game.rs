@@ -1,10 +1,30 @@
 use crate::prelude::*;
 pub mod actor;
+pub mod controls;
+pub mod economy;
+pub mod hazard;
+pub mod interact;
 pub mod item;
+pub mod room;
+pub mod settings;
+pub mod sfx;
+pub mod terrain;
 pub mod world;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((actor::plugin, world::plugin, item::plugin));
+    app.add_plugins((
+        actor::plugin,
+        controls::plugin,
+        world::plugin,
+        item::plugin,
+        economy::plugin,
+        hazard::plugin,
+        interact::plugin,
+        room::plugin,
+        settings::plugin,
+        sfx::plugin,
+        terrain::plugin,
+    ));
 }
 
 #[derive(PhysicsLayer, Default)]
@@ -15,4 +35,6 @@ pub enum GameLayer {
     Enemy,
     Projectile,
     Wall,
+    Pickup,
+    Hazard,
 }
@@ -1,20 +1,25 @@
+pub mod animation;
 pub mod camera_cutie;
 pub mod combat;
 pub mod enemy;
+pub mod experience;
 pub mod facing;
 pub mod movement;
 pub mod player;
 
+use crate::game::actor::animation::AnimationController;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure::<ActorAssets>();
     app.add_plugins((
+        animation::plugin,
         movement::plugin,
         facing::plugin,
         player::plugin,
         enemy::plugin,
         combat::plugin,
+        experience::plugin,
         camera_cutie::plugin,
     ));
 }
@@ -48,14 +53,13 @@ impl Configure for ActorAssets {
     }
 }
 
-fn create_entity_aseprite(sprite: Handle<Aseprite>, tag: &str) -> impl Bundle {
+fn create_entity_aseprite(sprite: Handle<Aseprite>, archetype: &str) -> impl Bundle {
     (
         AseAnimation {
             aseprite: sprite,
-            animation: Animation::tag(tag)
-                .with_repeat(AnimationRepeat::Loop)
-                .with_speed(1.75),
+            animation: Animation::default(),
         },
+        AnimationController::new(archetype),
         Sprite { ..default() },
         RigidBody::Dynamic,
         LockedAxes::ROTATION_LOCKED,
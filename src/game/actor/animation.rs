@@ -0,0 +1,291 @@
+use crate::game::actor::combat::damage::OnDamage;
+use crate::game::actor::facing::FacesLeft;
+use crate::game::actor::facing::Facing;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<AnimationConfig>,
+        AnimationController,
+        OnAnimationEnd,
+    )>();
+}
+
+/// Maps logical animation states to Aseprite tags for each actor archetype.
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnimationConfig {
+    pub archetypes: HashMap<String, AnimationTags>,
+}
+
+impl Config for AnimationConfig {
+    const FILE: &'static str = "animation.ron";
+}
+
+/// The Aseprite tags for a single archetype. Missing states fall back to another state.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct AnimationTags {
+    /// Playback speed multiplier for every tag.
+    pub speed: f32,
+    /// Falls back to the move tag for the current facing direction.
+    pub idle: Option<String>,
+    /// Move tags by direction. West-facing tags fall back to their east-facing mirror.
+    pub walk: HashMap<TagDirection, String>,
+    pub attack: Option<String>,
+    pub hurt: Option<String>,
+    pub die: Option<String>,
+    /// Whether the art faces left, for tags that aren't west- or east-facing move tags.
+    pub faces_left: bool,
+}
+
+impl Default for AnimationTags {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            idle: None,
+            walk: HashMap::default(),
+            attack: None,
+            hurt: None,
+            die: None,
+            faces_left: false,
+        }
+    }
+}
+
+impl AnimationTags {
+    fn get(&self, state: AnimationState, direction: Vec2) -> Option<&str> {
+        match state {
+            AnimationState::Idle => self.idle.as_deref().or_else(|| self.walk_tag(direction)),
+            AnimationState::Move => self.walk_tag(direction),
+            AnimationState::Attack => self.attack.as_deref(),
+            AnimationState::Hurt => self.hurt.as_deref(),
+            AnimationState::Die => self.die.as_deref(),
+        }
+    }
+
+    /// Whether the art for `tag` faces left.
+    fn faces_left(&self, tag: &str) -> bool {
+        let x = self
+            .walk
+            .iter()
+            .find(|(_, walk)| *walk == tag)
+            .map_or(0.0, |(key, _)| key.to_vec2().x);
+        if x == 0.0 { self.faces_left } else { x < 0.0 }
+    }
+
+    /// Find the walk tag with the closest direction, mirroring east-facing tags if necessary.
+    fn walk_tag(&self, direction: Vec2) -> Option<&str> {
+        let mirrored = vec2(-direction.x, direction.y);
+        self.walk
+            .iter()
+            .map(|(&key, tag)| {
+                let key_dir = key.to_vec2();
+                let mut distance = key_dir.angle_to(direction).abs();
+                if !self.walk.contains_key(&key.mirror()) {
+                    distance = distance.min(key_dir.angle_to(mirrored).abs());
+                }
+                (tag, distance)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(tag, _)| tag.as_str())
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TagDirection {
+    E,
+    NE,
+    N,
+    NW,
+    W,
+    SW,
+    S,
+    SE,
+}
+
+impl TagDirection {
+    fn to_vec2(self) -> Vec2 {
+        let (x, y) = match self {
+            Self::E => (1.0, 0.0),
+            Self::NE => (1.0, 1.0),
+            Self::N => (0.0, 1.0),
+            Self::NW => (-1.0, 1.0),
+            Self::W => (-1.0, 0.0),
+            Self::SW => (-1.0, -1.0),
+            Self::S => (0.0, -1.0),
+            Self::SE => (1.0, -1.0),
+        };
+        vec2(x, y).normalize()
+    }
+
+    fn mirror(self) -> Self {
+        match self {
+            Self::E => Self::W,
+            Self::NE => Self::NW,
+            Self::NW => Self::NE,
+            Self::W => Self::E,
+            Self::SW => Self::SE,
+            Self::SE => Self::SW,
+            x => x,
+        }
+    }
+}
+
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub enum AnimationState {
+    #[default]
+    Idle,
+    Move,
+    Attack,
+    Hurt,
+    Die,
+}
+
+/// Below this speed, an actor is considered idle.
+const MOVE_THRESHOLD: f32 = 5.0;
+
+/// Picks the [`AseAnimation`] tag from [`LinearVelocity`] and [`Facing`],
+/// using the tags for `archetype` in [`AnimationConfig`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct AnimationController {
+    pub archetype: String,
+    /// The state to return to after a one-shot state ends.
+    pub base: AnimationState,
+    /// A one-shot state to play on top of the base state.
+    pub one_shot: Option<AnimationState>,
+    current_tag: Option<String>,
+}
+
+impl Configure for AnimationController {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                end_one_shot_animations.in_set(UpdateSystems::HandleEvents),
+                update_animation_controller.in_set(UpdateSystems::SyncLate),
+            ),
+        );
+        app.add_observer(play_hurt_on_damage);
+    }
+}
+
+impl AnimationController {
+    pub fn new(archetype: impl Into<String>) -> Self {
+        Self {
+            archetype: archetype.into(),
+            base: AnimationState::Idle,
+            one_shot: None,
+            current_tag: None,
+        }
+    }
+
+    /// Play a one-shot state, then return to the base state.
+    pub fn play(&mut self, state: AnimationState) {
+        // Dying can't be interrupted.
+        if self.one_shot == Some(AnimationState::Die) {
+            return;
+        }
+        self.one_shot = Some(state);
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_animation_controller(
+    mut commands: Commands,
+    config: ConfigRef<AnimationConfig>,
+    mut controller_query: Query<(
+        Entity,
+        &mut AnimationController,
+        &mut AseAnimation,
+        Option<&LinearVelocity>,
+        Option<&Facing>,
+    )>,
+) {
+    let config = r!(config.get());
+    for (entity, mut controller, mut anim, velocity, facing) in &mut controller_query {
+        let tags = c!(config.archetypes.get(&controller.archetype));
+        let velocity = velocity.map_or(Vec2::ZERO, |x| x.0);
+        let moving = velocity.length_squared() >= MOVE_THRESHOLD.powi(2);
+        controller.base = if moving {
+            AnimationState::Move
+        } else {
+            AnimationState::Idle
+        };
+        let direction = if moving {
+            velocity
+        } else {
+            facing.map_or(Vec2::NEG_Y, |x| x.0.as_vec2())
+        };
+
+        // Skip one-shot states without a tag, so they still end.
+        if let Some(state) = controller.one_shot {
+            if tags.get(state, direction).is_none() {
+                controller.one_shot = None;
+                commands.entity(entity).trigger(OnAnimationEnd(state));
+            }
+        }
+
+        let state = controller.one_shot.unwrap_or(controller.base);
+        let tag = cq!(tags.get(state, direction));
+        cq!(controller.current_tag.as_deref() != Some(tag));
+
+        let repeat = if controller.one_shot.is_some() {
+            AnimationRepeat::Count(1)
+        } else {
+            AnimationRepeat::Loop
+        };
+        anim.animation = Animation::tag(tag)
+            .with_repeat(repeat)
+            .with_speed(tags.speed);
+        controller.current_tag = Some(tag.to_string());
+
+        // Flip the sprite relative to the direction of the chosen tag's art.
+        if tags.faces_left(tag) {
+            commands.entity(entity).insert(FacesLeft);
+        } else {
+            commands.entity(entity).remove::<FacesLeft>();
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn end_one_shot_animations(
+    mut commands: Commands,
+    mut animation_events: EventReader<AnimationEvents>,
+    mut controller_query: Query<&mut AnimationController>,
+) {
+    for event in animation_events.read() {
+        let AnimationEvents::Finished(entity) = *event else {
+            continue;
+        };
+        let mut controller = c!(controller_query.get_mut(entity));
+        let state = cq!(controller.one_shot);
+        cq!(state != AnimationState::Die);
+
+        controller.one_shot = None;
+        controller.current_tag = None;
+        commands.entity(entity).trigger(OnAnimationEnd(state));
+    }
+}
+
+fn play_hurt_on_damage(
+    trigger: Trigger<OnDamage>,
+    mut controller_query: Query<&mut AnimationController>,
+) {
+    let target = r!(trigger.get_target());
+    let mut controller = rq!(controller_query.get_mut(target));
+    controller.play(AnimationState::Hurt);
+}
+
+/// Triggered on an [`AnimationController`] when a one-shot state finishes.
+#[derive(Event, Reflect, Debug)]
+pub struct OnAnimationEnd(pub AnimationState);
+
+impl Configure for OnAnimationEnd {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
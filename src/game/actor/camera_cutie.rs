@@ -1,8 +1,31 @@
+use bevy::math::curve::Curve;
+use bevy::math::curve::EaseFunction;
+
+use crate::core::camera::CameraBounds;
+use crate::core::camera::CameraGroup;
+use crate::core::camera::CameraLookAhead;
+use crate::core::camera::CameraRoot;
+use crate::core::camera::CameraZoom;
 use crate::core::camera::SmoothFollow;
+use crate::game::actor::enemy::Enemy;
+use crate::game::actor::facing::Facing;
+use crate::game::actor::movement::input::PlayerAction;
+use crate::game::actor::player::Player;
+use crate::game::room::CurrentRoom;
+use crate::game::room::OnRoomEnter;
+use crate::game::room::Room;
+use crate::game::room::RoomKind;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::game::world::Level;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<CameraCutieEvent>();
+    app.configure::<(
+        CameraCutieEvent,
+        CameraSequence,
+        PlayCameraSequence,
+        RevealedRoom,
+    )>();
 }
 
 #[derive(Event, Reflect)]
@@ -17,6 +40,12 @@ impl Configure for CameraCutieEvent {
                 .in_set(UpdateSystems::SyncEarly)
                 .run_if(Pause::is_disabled),
         );
+        app.add_systems(
+            Update,
+            (update_camera_look_ahead, update_camera_group)
+                .in_set(UpdateSystems::SyncLate)
+                .run_if(Pause::is_disabled),
+        );
     }
 }
 
@@ -25,12 +54,364 @@ pub fn send_camera_follow_event(entity: Entity, mut event: EventWriter<CameraCut
 }
 
 fn camera_follow(
-    mut camera_query: Query<&mut SmoothFollow>,
+    mut camera_query: Query<(&mut SmoothFollow, Option<&mut CameraSequence>)>,
     mut ev_set: EventReader<CameraCutieEvent>,
 ) {
-    let mut smooth_follow = r!(camera_query.single_mut());
+    let (mut smooth_follow, mut sequence) = r!(camera_query.single_mut());
 
     for ev in ev_set.read() {
-        smooth_follow.target = ev.0;
+        // Don't interrupt a playing sequence, but return to the new target afterwards.
+        if let Some(sequence) = sequence.as_mut() {
+            sequence.return_target = ev.0;
+        } else {
+            smooth_follow.target = ev.0;
+        }
     }
 }
+
+/// Lead the camera in the direction its target is facing.
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_camera_look_ahead(
+    mut camera_query: Query<(&SmoothFollow, &mut CameraLookAhead)>,
+    facing_query: Query<&Facing>,
+) {
+    for (follow, mut look_ahead) in &mut camera_query {
+        look_ahead.direction = facing_query
+            .get(follow.target)
+            .map_or(Vec2::ZERO, |facing| facing.0.as_vec2());
+    }
+}
+
+/// Frame every player, plus the enemies in the current boss room.
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_camera_group(
+    camera_root: Res<CameraRoot>,
+    current_room: Res<CurrentRoom>,
+    mut group_query: Query<(&mut CameraGroup, Has<CameraSequence>)>,
+    player_query: Query<Entity, With<Player>>,
+    enemy_query: Query<(Entity, &GlobalTransform), With<Enemy>>,
+    room_query: Query<&Room>,
+) {
+    let (mut group, playing) = r!(group_query.get_mut(camera_root.primary));
+    group.targets.clear();
+    // Let camera sequences take full control.
+    rq!(!playing);
+
+    let boss_rect = current_room
+        .0
+        .and_then(|entity| room_query.get(entity).ok())
+        .filter(|room| room.kind == RoomKind::Boss)
+        .map(|room| room.rect);
+    group.targets.extend(&player_query);
+    if let Some(rect) = boss_rect {
+        group.targets.extend(
+            enemy_query
+                .iter()
+                .filter(|(_, gt)| rect.contains(gt.translation().xy()))
+                .map(|(entity, _)| entity),
+        );
+    }
+}
+
+/// A point for the camera to pan to.
+#[derive(Reflect, Copy, Clone, Debug)]
+pub enum CameraTarget {
+    Entity(Entity),
+    Position(Vec2),
+}
+
+/// A single step in a [`CameraSequence`].
+#[derive(Reflect, Copy, Clone, Debug)]
+pub enum CameraStep {
+    /// Pan to a target.
+    Pan {
+        target: CameraTarget,
+        secs: f32,
+        ease: EaseFunction,
+    },
+    /// Stay in place.
+    Hold { secs: f32 },
+    /// Zoom to a projection scale. Larger values zoom out.
+    Zoom {
+        scale: f32,
+        secs: f32,
+        ease: EaseFunction,
+    },
+    /// Pan back to the camera's original follow target.
+    Return { secs: f32, ease: EaseFunction },
+}
+
+impl CameraStep {
+    pub fn pan_to_entity(entity: Entity, secs: f32) -> Self {
+        Self::Pan {
+            target: CameraTarget::Entity(entity),
+            secs,
+            ease: EaseFunction::CubicInOut,
+        }
+    }
+
+    pub fn pan_to(position: Vec2, secs: f32) -> Self {
+        Self::Pan {
+            target: CameraTarget::Position(position),
+            secs,
+            ease: EaseFunction::CubicInOut,
+        }
+    }
+
+    pub fn hold(secs: f32) -> Self {
+        Self::Hold { secs }
+    }
+
+    pub fn zoom(scale: f32, secs: f32) -> Self {
+        Self::Zoom {
+            scale,
+            secs,
+            ease: EaseFunction::QuadraticInOut,
+        }
+    }
+
+    pub fn return_to_target(secs: f32) -> Self {
+        Self::Return {
+            secs,
+            ease: EaseFunction::CubicInOut,
+        }
+    }
+
+    fn secs(&self) -> f32 {
+        match *self {
+            Self::Pan { secs, .. }
+            | Self::Hold { secs }
+            | Self::Zoom { secs, .. }
+            | Self::Return { secs, .. } => secs,
+        }
+    }
+}
+
+/// A scripted camera sequence playing on the primary camera.
+///
+/// The camera follows an anchor entity that moves between steps, and gameplay input is
+/// blocked until the sequence ends or is skipped.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraSequence {
+    steps: Vec<CameraStep>,
+    index: usize,
+    elapsed: f32,
+    anchor: Entity,
+    /// The follow target to return to when the sequence ends.
+    return_target: Entity,
+    from_pos: Vec2,
+    from_zoom: f32,
+}
+
+impl Configure for CameraSequence {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                skip_camera_sequence.in_set(UpdateSystems::RecordInput),
+                play_camera_sequence.in_set(UpdateSystems::Update),
+            )
+                .run_if(Pause::is_disabled),
+        );
+        app.add_systems(StateFlush, Level::ANY.on_exit(stop_camera_sequence));
+    }
+}
+
+impl CameraSequence {
+    /// A run condition for blocking gameplay input during a sequence.
+    pub fn is_inactive(sequence_query: Query<(), With<CameraSequence>>) -> bool {
+        sequence_query.is_empty()
+    }
+}
+
+/// An event that starts a [`CameraSequence`] on the primary camera.
+#[derive(Event, Reflect, Debug)]
+pub struct PlayCameraSequence(pub Vec<CameraStep>);
+
+impl Configure for PlayCameraSequence {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(start_camera_sequence);
+    }
+}
+
+fn start_camera_sequence(
+    trigger: Trigger<PlayCameraSequence>,
+    mut commands: Commands,
+    camera_root: Res<CameraRoot>,
+    mut camera_query: Query<(
+        &Transform,
+        &mut SmoothFollow,
+        &mut CameraBounds,
+        &CameraZoom,
+        Has<CameraSequence>,
+    )>,
+) {
+    let (transform, mut follow, mut bounds, zoom, playing) =
+        r!(camera_query.get_mut(camera_root.primary));
+    rq!(!playing && !trigger.0.is_empty());
+
+    let pos = transform.translation.xy();
+    let anchor = commands
+        .spawn((
+            Name::new("CameraAnchor"),
+            Transform::from_translation(pos.extend(0.0)),
+            DespawnOnExitState::<Level>::default(),
+        ))
+        .id();
+    commands.entity(camera_root.primary).insert(CameraSequence {
+        steps: trigger.0.clone(),
+        index: 0,
+        elapsed: 0.0,
+        anchor,
+        return_target: follow.target,
+        from_pos: pos,
+        from_zoom: zoom.target,
+    });
+    follow.target = anchor;
+    // Let the camera leave the current room.
+    bounds.0 = None;
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn play_camera_sequence(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut current_room: ResMut<CurrentRoom>,
+    mut camera_query: Query<(
+        Entity,
+        &mut CameraSequence,
+        &mut SmoothFollow,
+        &mut CameraZoom,
+    )>,
+    mut transform_query: Query<&mut Transform, Without<CameraSequence>>,
+    target_query: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut sequence, mut follow, mut zoom) in &mut camera_query {
+        let step = *c!(sequence.steps.get(sequence.index));
+        sequence.elapsed += dt;
+        let mut t = if step.secs() > 0.0 {
+            (sequence.elapsed / step.secs()).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let mut anchor = c!(transform_query.get_mut(sequence.anchor));
+        match step {
+            CameraStep::Pan { target, ease, .. } => {
+                let to = match target {
+                    CameraTarget::Entity(target) => target_query
+                        .get(target)
+                        .map(|gt| gt.translation().xy())
+                        .ok(),
+                    CameraTarget::Position(pos) => Some(pos),
+                };
+                if let Some(to) = to {
+                    let pos = sequence.from_pos.lerp(to, ease.sample_clamped(t));
+                    anchor.translation = pos.extend(anchor.translation.z);
+                } else {
+                    // The target has despawned, so skip to the next step.
+                    t = 1.0;
+                }
+            },
+            CameraStep::Hold { .. } => {},
+            CameraStep::Zoom { scale, ease, .. } => {
+                zoom.target = sequence.from_zoom.lerp(scale, ease.sample_clamped(t));
+                zoom.current = zoom.target;
+            },
+            CameraStep::Return { ease, .. } => {
+                if let Ok(gt) = target_query.get(sequence.return_target) {
+                    let to = gt.translation().xy();
+                    let pos = sequence.from_pos.lerp(to, ease.sample_clamped(t));
+                    anchor.translation = pos.extend(anchor.translation.z);
+                } else {
+                    // The target has despawned, so skip to the next step.
+                    t = 1.0;
+                }
+            },
+        }
+        cq!(t >= 1.0);
+
+        sequence.index += 1;
+        sequence.elapsed = 0.0;
+        sequence.from_pos = anchor.translation.xy();
+        sequence.from_zoom = zoom.target;
+        if sequence.index >= sequence.steps.len() {
+            finish_camera_sequence(&mut commands, entity, &sequence, &mut follow);
+            // Restore the current room's camera settings.
+            current_room.set_changed();
+        }
+    }
+}
+
+fn finish_camera_sequence(
+    commands: &mut Commands,
+    camera: Entity,
+    sequence: &CameraSequence,
+    follow: &mut SmoothFollow,
+) {
+    follow.target = sequence.return_target;
+    commands.entity(sequence.anchor).try_despawn();
+    commands.entity(camera).remove::<CameraSequence>();
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn skip_camera_sequence(
+    mut commands: Commands,
+    mut current_room: ResMut<CurrentRoom>,
+    mut camera_query: Query<(Entity, &CameraSequence, &mut SmoothFollow)>,
+    action_query: Query<&ActionState<PlayerAction>>,
+) {
+    rq!(action_query
+        .iter()
+        .any(|action| action.just_pressed(&PlayerAction::Skip)));
+
+    for (entity, sequence, mut follow) in &mut camera_query {
+        finish_camera_sequence(&mut commands, entity, sequence, &mut follow);
+        current_room.set_changed();
+    }
+}
+
+fn stop_camera_sequence(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &CameraSequence, &mut SmoothFollow)>,
+) {
+    for (entity, sequence, mut follow) in &mut camera_query {
+        finish_camera_sequence(&mut commands, entity, sequence, &mut follow);
+    }
+}
+
+/// A [`Room`] that has already been revealed by a camera sequence.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct RevealedRoom;
+
+impl Configure for RevealedRoom {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(reveal_boss_room);
+    }
+}
+
+/// Show off the whole arena the first time the player enters a boss room.
+fn reveal_boss_room(
+    trigger: Trigger<OnRoomEnter>,
+    mut commands: Commands,
+    room_query: Query<&Room, Without<RevealedRoom>>,
+) {
+    let target = r!(trigger.get_target());
+    let room = rq!(room_query.get(target));
+    rq!(room.kind == RoomKind::Boss);
+
+    commands.entity(target).insert(RevealedRoom);
+    commands.trigger(PlaySfx::new(SfxId::BossRoar).with_pitch_variance(0.0));
+    commands.trigger(PlayCameraSequence(vec![
+        CameraStep::pan_to(room.rect.center(), 1.2),
+        CameraStep::zoom(room.camera.zoom.max(1.5), 0.8),
+        CameraStep::hold(1.0),
+        CameraStep::return_to_target(0.8),
+    ]));
+}
@@ -1,9 +1,17 @@
 pub mod damage;
+pub mod damage_number;
 pub mod heal;
 pub mod health;
+pub mod shield;
 
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((health::plugin, damage::plugin, heal::plugin));
+    app.add_plugins((
+        health::plugin,
+        damage::plugin,
+        damage_number::plugin,
+        heal::plugin,
+        shield::plugin,
+    ));
 }
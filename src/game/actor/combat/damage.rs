@@ -1,8 +1,11 @@
 use crate::game::actor::combat::health::Health;
+use crate::game::actor::combat::shield::Armor;
+use crate::game::actor::combat::shield::Shield;
+use crate::game::actor::movement::ExternalVelocity;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(Damage, OnDamage)>();
+    app.configure::<(Damage, Knockback, OnDamage, OnDamaged)>();
 }
 
 #[derive(Component, Reflect, Debug, Default)]
@@ -15,15 +18,79 @@ impl Configure for Damage {
     }
 }
 
+/// Pushes entities away on collision by applying an impulse to their [`ExternalVelocity`].
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Knockback(pub f32);
+
+impl Configure for Knockback {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(apply_knockback_on_collision);
+    }
+}
+
+fn apply_knockback_on_collision(
+    trigger: Trigger<OnCollisionStart>,
+    knockback_query: Query<(&Knockback, &GlobalTransform)>,
+    mut target_query: Query<(
+        Option<&mut ExternalVelocity>,
+        Option<&mut LinearVelocity>,
+        &GlobalTransform,
+    )>,
+) {
+    let attacker = r!(trigger.get_target());
+    let (knockback, attacker_gt) = rq!(knockback_query.get(attacker));
+    let target = trigger.body.unwrap_or(trigger.collider);
+    let (external, velocity, target_gt) = rq!(target_query.get_mut(target));
+
+    let direction = target_gt.translation().xy() - attacker_gt.translation().xy();
+    let impulse = knockback.0 * rq!(Dir2::new(direction));
+    if let Some(mut external) = external {
+        external.apply_impulse(impulse);
+    } else if let Some(mut velocity) = velocity {
+        // Without `ExternalVelocity`, the physics engine owns `LinearVelocity`.
+        velocity.0 += impulse;
+    }
+}
+
+/// The source of an [`OnDamage`], for effects that react to specific damage types.
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub enum DamageKind {
+    #[default]
+    Physical,
+    Fire,
+    Poison,
+    Explosive,
+}
+
 #[derive(Event, Reflect, Debug)]
 pub struct OnDamage {
     pub damage: f32,
     pub attacker: Option<Entity>,
+    pub kind: DamageKind,
+    /// Whether this is a damage-over-time tick instead of a direct hit.
+    pub is_tick: bool,
 }
 
 impl OnDamage {
     pub fn new(damage: f32, attacker: Option<Entity>) -> Self {
-        Self { damage, attacker }
+        Self {
+            damage,
+            attacker,
+            kind: DamageKind::Physical,
+            is_tick: false,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: DamageKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn as_tick(mut self) -> Self {
+        self.is_tick = true;
+        self
     }
 }
 
@@ -35,10 +102,42 @@ impl Configure for OnDamage {
     }
 }
 
-fn decrease_health_on_damage(trigger: Trigger<OnDamage>, mut health_query: Query<&mut Health>) {
+/// Reports the damage an [`OnDamage`] actually dealt to [`Health`], after shield and armor.
+#[derive(Event, Reflect, Debug)]
+pub struct OnDamaged {
+    pub damage: f32,
+    pub attacker: Option<Entity>,
+}
+
+impl Configure for OnDamaged {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+fn decrease_health_on_damage(
+    trigger: Trigger<OnDamage>,
+    mut commands: Commands,
+    mut health_query: Query<(&mut Health, Option<&mut Shield>, Option<&Armor>)>,
+) {
     let target = r!(trigger.get_target());
-    let mut target_health = r!(health_query.get_mut(target));
-    target_health.current -= trigger.damage;
+    let (mut target_health, shield, armor) = r!(health_query.get_mut(target));
+
+    // Absorb damage in order: shield, then armor, then health.
+    let mut damage = trigger.damage;
+    if let Some(mut shield) = shield {
+        damage = shield.absorb(damage);
+    }
+    if let Some(armor) = armor {
+        damage = armor.reduce(damage);
+    }
+    let dealt = damage.clamp(0.0, target_health.current.max(0.0));
+    target_health.current -= damage;
+
+    commands.entity(target).trigger(OnDamaged {
+        damage: dealt,
+        attacker: trigger.attacker,
+    });
 }
 
 fn deal_damage_on_collision(
@@ -0,0 +1,89 @@
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::actor::combat::damage::OnDamage;
+use crate::game::settings::GameplaySettings;
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<DamageNumber>();
+}
+
+/// A floating number that shows damage dealt, then rises and fades out.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DamageNumber {
+    pub velocity: Vec2,
+    pub remaining: f32,
+}
+
+impl Configure for DamageNumber {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            update_damage_numbers
+                .in_set(UpdateSystems::Update)
+                .in_set(PausableSystems),
+        );
+        app.add_observer(spawn_damage_number);
+    }
+}
+
+const DAMAGE_NUMBER_SECS: f32 = 0.8;
+
+fn spawn_damage_number(
+    trigger: Trigger<OnDamage>,
+    mut commands: Commands,
+    gameplay_settings: Res<GameplaySettings>,
+    gt_query: Query<&GlobalTransform>,
+) {
+    rq!(gameplay_settings.damage_numbers);
+    rq!(trigger.damage >= 0.5);
+    let target = r!(trigger.get_target());
+    let pos = rq!(gt_query.get(target)).translation().xy();
+    let color = match trigger.kind {
+        DamageKind::Physical => Color::WHITE,
+        DamageKind::Fire => ORANGE_400.into(),
+        DamageKind::Poison => LIME_400.into(),
+        DamageKind::Explosive => YELLOW_300.into(),
+    };
+    let jitter = thread_rng().gen_range(-8.0..=8.0);
+
+    commands.spawn((
+        Name::new("DamageNumber"),
+        Text2d::new(format!("{:.0}", trigger.damage)),
+        TextFont {
+            font: BOLD_FONT_HANDLE,
+            font_size: 10.0,
+            ..default()
+        },
+        TextColor(color),
+        Transform::from_translation((pos + vec2(jitter, 24.0)).extend(50.0)),
+        DamageNumber {
+            velocity: vec2(0.0, 40.0),
+            remaining: DAMAGE_NUMBER_SECS,
+        },
+        DespawnOnExitState::<Level>::default(),
+    ));
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_damage_numbers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut number, mut transform, mut color) in &mut number_query {
+        number.remaining -= dt;
+        if number.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (number.velocity * dt).extend(0.0);
+        color
+            .0
+            .set_alpha((2.0 * number.remaining / DAMAGE_NUMBER_SECS).min(1.0));
+    }
+}
@@ -1,12 +1,65 @@
+use crate::animation::PostColorSystems;
+use crate::animation::backup::SpriteColorBackup;
 use crate::game::actor::combat::health::Health;
+use crate::game::actor::combat::shield::Shield;
+use crate::game::settings::AccessibilitySettings;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<OnHeal>();
+    app.configure::<(OnHeal, OnHealed, HealFlash)>();
 }
 
-#[derive(Event, Reflect, Debug)]
-pub struct OnHeal(pub f32);
+/// How much an [`OnHeal`] restores.
+#[derive(Reflect, Copy, Clone, Debug)]
+pub enum HealAmount {
+    /// A fixed amount of health.
+    Flat(f32),
+    /// A fraction of the target's max health.
+    Percent(f32),
+}
+
+impl Default for HealAmount {
+    fn default() -> Self {
+        Self::Flat(0.0)
+    }
+}
+
+impl HealAmount {
+    pub fn resolve(self, health: &Health) -> f32 {
+        match self {
+            Self::Flat(amount) => amount,
+            Self::Percent(fraction) => fraction * health.max,
+        }
+    }
+}
+
+#[derive(Event, Reflect, Copy, Clone, Default, Debug)]
+pub struct OnHeal {
+    pub amount: HealAmount,
+    /// Convert healing past max health into [`Shield`].
+    pub overheal_to_shield: bool,
+}
+
+impl OnHeal {
+    pub fn flat(amount: f32) -> Self {
+        Self {
+            amount: HealAmount::Flat(amount),
+            overheal_to_shield: false,
+        }
+    }
+
+    pub fn percent(fraction: f32) -> Self {
+        Self {
+            amount: HealAmount::Percent(fraction),
+            overheal_to_shield: false,
+        }
+    }
+
+    pub fn with_overheal_to_shield(mut self) -> Self {
+        self.overheal_to_shield = true;
+        self
+    }
+}
 
 impl Configure for OnHeal {
     fn configure(app: &mut App) {
@@ -15,8 +68,120 @@ impl Configure for OnHeal {
     }
 }
 
-fn apply_heal(trigger: Trigger<OnHeal>, mut health_query: Query<&mut Health>) {
+fn apply_heal(
+    trigger: Trigger<OnHeal>,
+    mut commands: Commands,
+    mut health_query: Query<(&mut Health, Option<&mut Shield>)>,
+) {
+    let target = r!(trigger.get_target());
+    let (mut health, shield) = r!(health_query.get_mut(target));
+    let amount = trigger.amount.resolve(&health).max(0.0);
+
+    // Clamp here instead of waiting for `clamp_health` so the restored amount is exact.
+    let before = health.current.clamp(0.0, health.max);
+    health.current = (before + amount).min(health.max);
+    let restored = health.current - before;
+
+    let overheal = amount - restored;
+    let shielded = if trigger.overheal_to_shield && overheal > 0.0 {
+        if let Some(mut shield) = shield {
+            shield.restore(overheal)
+        } else {
+            let shield = Shield {
+                max: health.max,
+                current: overheal.min(health.max),
+                ..default()
+            };
+            let shielded = shield.current;
+            commands.entity(target).insert(shield);
+            shielded
+        }
+    } else {
+        0.0
+    };
+
+    commands.entity(target).trigger(OnHealed {
+        health: restored,
+        shield: shielded,
+    });
+}
+
+/// Reports the amount an [`OnHeal`] actually restored.
+#[derive(Event, Reflect, Debug)]
+pub struct OnHealed {
+    pub health: f32,
+    pub shield: f32,
+}
+
+impl Configure for OnHealed {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(flash_on_healed);
+    }
+}
+
+fn flash_on_healed(
+    trigger: Trigger<OnHealed>,
+    mut commands: Commands,
+    accessibility_settings: Res<AccessibilitySettings>,
+    sprite_query: Query<(), With<Sprite>>,
+) {
+    rq!(!accessibility_settings.reduce_flashing);
     let target = r!(trigger.get_target());
-    let mut health = r!(health_query.get_mut(target));
-    health.current += trigger.0;
+    rq!(trigger.health > 0.0 || trigger.shield > 0.0);
+    rq!(sprite_query.contains(target));
+
+    commands
+        .entity(target)
+        .insert(HealFlash::new(HEAL_FLASH_SECS));
+}
+
+const HEAL_FLASH_SECS: f32 = 0.4;
+const HEAL_FLASH_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+
+/// Tints a sprite to show that it received healing.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(SpriteColorBackup)]
+pub struct HealFlash {
+    duration: f32,
+    remaining: f32,
+}
+
+impl Configure for HealFlash {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(PostUpdate, apply_heal_flash.in_set(PostColorSystems::Blend));
+    }
+}
+
+impl HealFlash {
+    fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            remaining: duration,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_heal_flash(
+    time: Res<Time>,
+    mut late: LateCommands,
+    mut flash_query: Query<(Entity, &mut HealFlash, &mut Sprite)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut flash, mut sprite) in &mut flash_query {
+        let t = (flash.remaining / flash.duration).max(0.0);
+        // Blend multiplicatively so the flash stacks with other color effects.
+        let tint = Color::WHITE.mix(&HEAL_FLASH_COLOR, t).to_linear();
+        sprite.color =
+            LinearRgba::from_vec4(sprite.color.to_linear().to_vec4() * tint.to_vec4()).into();
+        if flash.remaining <= 0.0 {
+            late.commands()
+                .entity(entity)
+                .remove::<(HealFlash, SpriteColorBackup)>();
+        }
+        flash.remaining -= dt;
+    }
 }
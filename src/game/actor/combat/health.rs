@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<Health>();
+    app.configure::<(Health, OnDeath, Revivable, Downed, OnDowned, OnRevived)>();
 }
 
 #[derive(Component, Reflect, Debug)]
@@ -30,12 +30,79 @@ impl Health {
     }
 }
 
-fn handle_death(mut commands: Commands, health_query: Query<(Entity, &Health), Changed<Health>>) {
-    for (entity, health) in &health_query {
+fn handle_death(
+    mut commands: Commands,
+    health_query: Query<(Entity, &Health, Has<Revivable>, Has<Downed>), Changed<Health>>,
+) {
+    for (entity, health, revivable, downed) in &health_query {
         if health.current >= f32::EPSILON {
+            if downed {
+                commands
+                    .entity(entity)
+                    .remove::<Downed>()
+                    .trigger(OnRevived);
+            }
             continue;
         }
-        commands.entity(entity).despawn();
+        if revivable {
+            if !downed {
+                commands.entity(entity).insert(Downed).trigger(OnDowned);
+            }
+            continue;
+        }
+        commands.entity(entity).trigger(OnDeath).despawn();
+    }
+}
+
+/// Triggered on an entity right before it despawns from running out of [`Health`].
+#[derive(Event, Reflect, Debug)]
+pub struct OnDeath;
+
+impl Configure for OnDeath {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// An entity that is [`Downed`] instead of despawned when it runs out of [`Health`].
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Revivable;
+
+impl Configure for Revivable {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// A [`Revivable`] entity with no [`Health`] left. Healing it revives it.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Downed;
+
+impl Configure for Downed {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Triggered on a [`Revivable`] entity when it becomes [`Downed`].
+#[derive(Event, Reflect, Debug)]
+pub struct OnDowned;
+
+impl Configure for OnDowned {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Triggered on a [`Downed`] entity when it is healed back up.
+#[derive(Event, Reflect, Debug)]
+pub struct OnRevived;
+
+impl Configure for OnRevived {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
     }
 }
 
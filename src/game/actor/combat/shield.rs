@@ -0,0 +1,114 @@
+use crate::game::actor::combat::damage::OnDamage;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Shield, Armor)>();
+}
+
+/// A pool that absorbs damage before [`Armor`] and [`Health`](super::health::Health).
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Shield {
+    pub max: f32,
+    pub current: f32,
+    /// Seconds without taking damage before regeneration starts.
+    pub regen_delay: f32,
+    /// Amount regenerated per second.
+    pub regen_rate: f32,
+    /// Seconds since the last time damage was taken.
+    pub since_damage: f32,
+}
+
+impl Configure for Shield {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            regenerate_shield
+                .in_set(UpdateSystems::Update)
+                .run_if(Pause::is_disabled),
+        );
+        app.add_observer(reset_shield_regen_on_damage);
+    }
+}
+
+impl Shield {
+    pub fn new(max: f32) -> Self {
+        Self {
+            max,
+            current: max,
+            ..default()
+        }
+    }
+
+    pub fn with_regen(mut self, delay: f32, rate: f32) -> Self {
+        self.regen_delay = delay;
+        self.regen_rate = rate;
+        self
+    }
+
+    /// Add up to `amount` to the pool and return how much was actually added.
+    pub fn restore(&mut self, amount: f32) -> f32 {
+        let before = self.current;
+        self.current = (self.current + amount).clamp(0.0, self.max);
+        self.current - before
+    }
+
+    /// Absorb up to `damage` and return the damage left over.
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let absorbed = damage.clamp(0.0, self.current);
+        self.current -= absorbed;
+        damage - absorbed
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn regenerate_shield(time: Res<Time>, mut shield_query: Query<&mut Shield>) {
+    let dt = time.delta_secs();
+    for mut shield in &mut shield_query {
+        shield.since_damage += dt;
+        if shield.since_damage < shield.regen_delay || shield.current >= shield.max {
+            continue;
+        }
+
+        let rate = shield.regen_rate;
+        shield.restore(rate * dt);
+    }
+}
+
+fn reset_shield_regen_on_damage(trigger: Trigger<OnDamage>, mut shield_query: Query<&mut Shield>) {
+    let target = r!(trigger.get_target());
+    let mut shield = rq!(shield_query.get_mut(target));
+    shield.since_damage = 0.0;
+}
+
+/// Reduces incoming damage that gets past [`Shield`].
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Armor {
+    /// Subtracted from each hit.
+    pub flat: f32,
+    /// Fraction of the remaining damage to ignore, from 0 to 1.
+    pub percent: f32,
+}
+
+impl Configure for Armor {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Armor {
+    pub fn flat(flat: f32) -> Self {
+        Self { flat, percent: 0.0 }
+    }
+
+    pub fn percent(percent: f32) -> Self {
+        Self { flat: 0.0, percent }
+    }
+
+    /// Return the damage left over after armor.
+    pub fn reduce(&self, damage: f32) -> f32 {
+        (damage - self.flat).max(0.0) * (1.0 - self.percent.clamp(0.0, 1.0))
+    }
+}
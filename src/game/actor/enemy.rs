@@ -1,6 +1,9 @@
 use crate::game::GameLayer;
 use crate::game::actor::combat::health::Health;
+use crate::game::actor::experience::DropsExperience;
+use crate::game::actor::facing::FacingSource;
 use crate::game::actor::{create_entity_aseprite, create_entity_image};
+use crate::game::economy::DropsCurrency;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
@@ -27,13 +30,15 @@ pub fn get_enemy(name: &'static str, texture: Handle<Image>) -> impl Bundle {
         CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL),
         ExternalForce::new(Vec2::ZERO).with_persistence(false),
         Restitution::new(0.75),
+        DropsCurrency::default(),
+        DropsExperience::default(),
     )
 }
 
 pub fn get_enemy_aseprite(
     name: &'static str,
     texture: Handle<Aseprite>,
-    tag: &str,
+    archetype: &str,
     health: f32,
     length: f32,
 ) -> impl Bundle {
@@ -41,10 +46,13 @@ pub fn get_enemy_aseprite(
         Name::new(name),
         Enemy,
         Health::new(health),
-        create_entity_aseprite(texture, tag),
+        create_entity_aseprite(texture, archetype),
+        FacingSource::Movement,
         Collider::rectangle(length, length),
         CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL),
         ExternalForce::new(Vec2::ZERO).with_persistence(false),
         Restitution::new(0.75),
+        DropsCurrency::default(),
+        DropsExperience::default(),
     )
 }
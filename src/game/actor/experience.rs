@@ -0,0 +1,116 @@
+use crate::game::actor::ActorAssets;
+use crate::game::actor::combat::health::OnDeath;
+use crate::game::actor::player::Player;
+use crate::game::item::pickup::pickup;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Experience, DropsExperience, ExperiencePickup)>();
+}
+
+/// A player's level and the experience gained towards the next level.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Experience {
+    pub level: u32,
+    pub current: u32,
+}
+
+impl Configure for Experience {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            current: 0,
+        }
+    }
+}
+
+impl Experience {
+    /// The experience needed to reach the next level.
+    pub fn next_level(&self) -> u32 {
+        100 * self.level
+    }
+
+    /// Gain `amount` experience, levelling up as many times as it allows.
+    pub fn add(&mut self, amount: u32) {
+        self.current += amount;
+        while self.current >= self.next_level() {
+            self.current -= self.next_level();
+            self.level += 1;
+        }
+    }
+}
+
+/// Drops an experience cheese worth the given amount on death.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DropsExperience(pub u32);
+
+impl Configure for DropsExperience {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(drop_experience_on_death);
+    }
+}
+
+impl Default for DropsExperience {
+    fn default() -> Self {
+        Self(10)
+    }
+}
+
+fn drop_experience_on_death(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    actor_assets: Res<ActorAssets>,
+    drop_query: Query<(&DropsExperience, &GlobalTransform)>,
+) {
+    let target = r!(trigger.get_target());
+    let (drop, gt) = rq!(drop_query.get(target));
+    rq!(drop.0 > 0);
+
+    commands.spawn((
+        pickup("Experience Cheese", actor_assets.exp_cheese.clone()),
+        ExperiencePickup(drop.0),
+        Transform::from_translation(gt.translation().xy().extend(4.0)),
+        DespawnOnExitState::<Level>::default(),
+    ));
+}
+
+/// Experience lying in the world, waiting to be collected by a player.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ExperiencePickup(pub u32);
+
+impl Configure for ExperiencePickup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(collect_experience);
+    }
+}
+
+fn collect_experience(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    pickup_query: Query<&ExperiencePickup>,
+    mut player_query: Query<&mut Experience, With<Player>>,
+) {
+    let pickup_entity = r!(trigger.get_target());
+    let pickup = rq!(pickup_query.get(pickup_entity));
+    let body = rq!(trigger.body);
+    let mut experience = rq!(player_query.get_mut(body));
+
+    experience.add(pickup.0);
+    commands
+        .entity(body)
+        .trigger(PlaySfx::new(SfxId::Pickup).with_pitch_variance(0.25));
+    commands.entity(pickup_entity).despawn();
+}
@@ -1,12 +1,16 @@
+use crate::animation::PostTransformSystems;
 use crate::core::camera::CameraRoot;
 use crate::core::window::WindowRoot;
+use crate::game::actor::combat::health::Downed;
+use crate::game::actor::movement::input::PlayerAction;
 use crate::game::actor::player::Player;
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(Facing, FacePlayer)>();
+    app.configure::<(Facing, FacingSource, FacesLeft)>();
 }
 
+/// The direction an actor is facing. This is the single source of truth for sprite flipping.
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct Facing(pub Dir2);
@@ -14,7 +18,10 @@ pub struct Facing(pub Dir2);
 impl Configure for Facing {
     fn configure(app: &mut App) {
         app.register_type::<Facing>();
-        app.add_systems(Update, apply_facing_to_sprite.in_set(UpdateSystems::Update));
+        app.add_systems(
+            PostUpdate,
+            apply_facing.in_set(PostTransformSystems::ApplyFacing),
+        );
     }
 }
 
@@ -24,73 +31,128 @@ impl Default for Facing {
     }
 }
 
-fn apply_facing_to_sprite(mut facing_query: Query<(&Facing, &mut Sprite)>) {
-    for (facing, mut sprite) in &mut facing_query {
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_facing(mut facing_query: Query<(&Facing, &mut Sprite, Has<FacesLeft>)>) {
+    for (facing, mut sprite, faces_left) in &mut facing_query {
         if facing.0.x != 0.0 {
-            sprite.flip_x = facing.0.x < 0.0;
+            sprite.flip_x = (facing.0.x < 0.0) != faces_left;
         }
     }
 }
 
+/// Marks a sprite whose art faces left instead of right.
+///
+/// Kept in sync with the current tag by [`AnimationController`].
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-pub struct FacePlayer;
+pub struct FacesLeft;
 
-impl Configure for FacePlayer {
+impl Configure for FacesLeft {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
-        app.add_systems(
-            Update,
-            face_player
-                .in_set(UpdateSystems::SyncEarly)
-                .run_if(Pause::is_disabled),
-        );
     }
 }
 
-fn face_player(
-    player_query: Query<&GlobalTransform, With<Player>>,
-    mut facing_query: Query<(&mut Facing, &GlobalTransform), With<FacePlayer>>,
-) {
-    let target_pos = rq!(player_query.single()).translation().xy();
-
-    for (mut facing, gt) in &mut facing_query {
-        let pos = gt.translation().xy();
-        facing.0 = c!(Dir2::new(target_pos - pos));
-    }
-}
-
-#[derive(Component, Reflect, Default)]
+/// The policy for updating [`Facing`].
+#[derive(Component, Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
 #[reflect(Component)]
-pub struct FaceCursor;
+#[require(Facing)]
+pub enum FacingSource {
+    /// Never update facing automatically.
+    Fixed,
+    /// Face the direction of [`LinearVelocity`].
+    #[default]
+    Movement,
+    /// Face the cursor.
+    Cursor,
+    /// Face the gamepad aim stick, falling back to the cursor.
+    Aim,
+    /// Face the gamepad aim stick, falling back to [`LinearVelocity`].
+    AimStick,
+    /// Face the nearest living player.
+    Player,
+}
 
-impl Configure for FaceCursor {
+impl Configure for FacingSource {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
         app.add_systems(
             Update,
-            face_cursor
-                .in_set(UpdateSystems::SyncEarly)
+            update_facing
+                .in_set(UpdateSystems::SyncLate)
                 .run_if(Pause::is_disabled),
         );
     }
 }
 
-fn face_cursor(
+/// Below this speed, [`FacingSource::Movement`] keeps the previous facing.
+const MOVEMENT_FACING_THRESHOLD: f32 = 5.0;
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_facing(
     window_root: Res<WindowRoot>,
     window_query: Query<&Window>,
     camera_root: Res<CameraRoot>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
-    mut facing_query: Query<(&mut Facing, &GlobalTransform), With<FaceCursor>>,
+    player_query: Query<&GlobalTransform, (With<Player>, Without<Downed>)>,
+    mut facing_query: Query<(
+        &mut Facing,
+        &FacingSource,
+        &GlobalTransform,
+        Option<&LinearVelocity>,
+        Option<&ActionState<PlayerAction>>,
+    )>,
 ) {
-    let window = r!(window_query.get(window_root.primary));
-    let (camera, camera_gt) = r!(camera_query.get(camera_root.primary));
-    let cursor_pos = rq!(window
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_gt, cursor).ok()));
+    let cursor_pos = window_query
+        .get(window_root.primary)
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get(camera_root.primary).ok())
+        .and_then(|(cursor, (camera, camera_gt))| {
+            camera.viewport_to_world_2d(camera_gt, cursor).ok()
+        });
+    let player_positions = player_query
+        .iter()
+        .map(|gt| gt.translation().xy())
+        .collect::<Vec<_>>();
 
-    for (mut facing, gt) in &mut facing_query {
+    for (mut facing, source, gt, velocity, action) in &mut facing_query {
         let pos = gt.translation().xy();
-        facing.0 = c!(Dir2::new(cursor_pos - pos));
+        let direction = match source {
+            FacingSource::Fixed => continue,
+            FacingSource::Movement => cq!(movement_direction(velocity)),
+            FacingSource::Cursor => cq!(cursor_pos) - pos,
+            FacingSource::Aim => {
+                let aim = action.map_or(Vec2::ZERO, |x| x.axis_pair(&PlayerAction::Aim));
+                if aim != Vec2::ZERO {
+                    aim
+                } else {
+                    cq!(cursor_pos) - pos
+                }
+            },
+            FacingSource::AimStick => {
+                let aim = action.map_or(Vec2::ZERO, |x| x.axis_pair(&PlayerAction::Aim));
+                if aim != Vec2::ZERO {
+                    aim
+                } else {
+                    cq!(movement_direction(velocity))
+                }
+            },
+            FacingSource::Player => cq!(nearest(&player_positions, pos)) - pos,
+        };
+        facing.0 = c!(Dir2::new(direction));
     }
 }
+
+fn movement_direction(velocity: Option<&LinearVelocity>) -> Option<Vec2> {
+    let velocity = velocity?.0;
+    (velocity.length_squared() >= MOVEMENT_FACING_THRESHOLD.powi(2)).then_some(velocity)
+}
+
+/// The closest of `points` to `pos`.
+fn nearest(points: &[Vec2], pos: Vec2) -> Option<Vec2> {
+    points
+        .iter()
+        .copied()
+        .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+}
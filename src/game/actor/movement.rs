@@ -1,17 +1,18 @@
 pub mod input;
 pub mod spring;
 
+use crate::game::terrain::Terrain;
 use crate::prelude::*;
-use std::cmp::Ordering;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(Movement, MovementController)>();
+    app.configure::<(Movement, MovementController, AppliedVelocity)>();
     app.add_plugins((input::plugin, spring::plugin));
 }
 
 #[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone, Default)]
 #[reflect(Component)]
 #[serde(default)]
+#[require(SelfVelocity, ExternalVelocity, AppliedVelocity)]
 pub struct Movement {
     pub accel: f32,
     pub decel: f32,
@@ -28,6 +29,26 @@ impl Movement {
             direction,
         }
     }
+
+    /// Step a self-propelled velocity towards `input` (clamped to length 1) times max speed.
+    pub fn step(&self, velocity: Vec2, input: Vec2, dt: f32) -> Vec2 {
+        if input == Vec2::ZERO {
+            move_towards(velocity, Vec2::ZERO, self.decel * dt)
+        } else {
+            let target = input.clamp_length_max(1.0) * self.speed;
+            move_towards(velocity, target, self.accel * dt)
+        }
+    }
+}
+
+fn move_towards(from: Vec2, to: Vec2, max_delta: f32) -> Vec2 {
+    let delta = to - from;
+    let distance = delta.length();
+    if distance <= max_delta || distance == 0.0 {
+        to
+    } else {
+        from + delta / distance * max_delta
+    }
 }
 
 impl Configure for Movement {
@@ -42,49 +63,124 @@ impl Configure for Movement {
     }
 }
 
+#[cfg_attr(feature = "native_dev", hot)]
 fn apply_movement(
     time: Res<Time>,
     mut movement_query: Query<(
         &Movement,
         &mut MovementController,
+        &mut SelfVelocity,
+        &mut ExternalVelocity,
+        &mut AppliedVelocity,
         &mut LinearVelocity,
-        &mut Sprite,
+        Option<&Terrain>,
     )>,
 ) {
     let dt = time.delta_secs();
+    for (
+        movement,
+        mut controller,
+        mut self_velocity,
+        mut external,
+        mut applied,
+        mut velocity,
+        terrain,
+    ) in &mut movement_query
+    {
+        // Keep what the physics engine changed since last frame (e.g. collisions and restitution).
+        // Slowing down along the walking direction slows down walking, and the rest decays like
+        // an impulse, so neither is undone or applied twice.
+        let mut residual = velocity.0 - applied.0;
+        let direction = self_velocity.0.normalize_or_zero();
+        let blocked = residual
+            .dot(direction)
+            .clamp(-self_velocity.0.length(), 0.0)
+            * direction;
+        self_velocity.0 += blocked;
+        residual -= blocked;
+        external.impulse += residual;
+        applied.0 = velocity.0;
 
-    for (movement, mut controller, mut velocity, mut sprite) in &mut movement_query {
-        if controller.0 == Vec2::ZERO || velocity.0.length_squared() >= movement.speed.powi(2) {
-            if velocity.x != 0.0 {
-                let sign_x = velocity.x.signum();
-                velocity.x -= movement.decel * time.delta_secs() * sign_x;
-                if velocity.x.signum() != sign_x {
-                    velocity.x = 0.0
-                }
-            }
-            if velocity.y != 0.0 {
-                let sign_y = velocity.y.signum();
-                velocity.y -= movement.decel * time.delta_secs() * sign_y;
-                if velocity.y.signum() != sign_y {
-                    velocity.y = 0.0
-                }
-            }
-        } else {
-            // Apply acceleration
-            velocity.0 =
-                (velocity.0 + movement.accel * controller.0 * dt).clamp_length_max(movement.speed);
-
-            let flip = match velocity.0.x.partial_cmp(&0.0).unwrap() {
-                Ordering::Less => false,
-                Ordering::Equal => false,
-                Ordering::Greater => true,
-            };
-            sprite.flip_x = flip;
-            controller.0 = Vec2::ZERO;
+        let movement = &terrain.map_or(*movement, |x| x.modifier.apply(movement));
+        self_velocity.0 = movement.step(self_velocity.0, controller.0, dt);
+        controller.0 = Vec2::ZERO;
+        external.step(dt);
+
+        // Apply only the change in this system's contribution.
+        let contribution = self_velocity.0 + external.total();
+        velocity.0 += contribution - applied.0;
+        applied.0 = contribution;
+        external.carry = Vec2::ZERO;
+    }
+}
+
+/// The velocity that movement contributed to [`LinearVelocity`] last frame.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct AppliedVelocity(pub Vec2);
+
+impl Configure for AppliedVelocity {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// The part of [`LinearVelocity`] that an actor produces by walking.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct SelfVelocity(pub Vec2);
+
+impl Configure for SelfVelocity {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// The part of [`LinearVelocity`] that comes from outside forces.
+///
+/// This is layered on top of [`SelfVelocity`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ExternalVelocity {
+    /// Velocity from impulses like knockback, explosions and dashes. Decays over time.
+    pub impulse: Vec2,
+    /// Deceleration of [`Self::impulse`] per second.
+    pub decay: f32,
+    /// Velocity that only lasts for the current frame, like a conveyor floor.
+    /// Should be set before [`UpdateSystems::Update`].
+    pub carry: Vec2,
+}
+
+impl Configure for ExternalVelocity {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for ExternalVelocity {
+    fn default() -> Self {
+        Self {
+            impulse: Vec2::ZERO,
+            decay: 2000.0,
+            carry: Vec2::ZERO,
         }
     }
 }
 
+impl ExternalVelocity {
+    pub fn apply_impulse(&mut self, impulse: Vec2) {
+        self.impulse += impulse;
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.impulse = move_towards(self.impulse, Vec2::ZERO, self.decay * dt);
+    }
+
+    pub fn total(&self) -> Vec2 {
+        self.impulse + self.carry
+    }
+}
+
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct MovementController(pub Vec2);
@@ -94,3 +190,88 @@ impl Configure for MovementController {
         app.register_type::<Self>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 0.1;
+
+    fn movement() -> Movement {
+        Movement::new(100.0, 200.0, 50.0, 1.0)
+    }
+
+    #[test]
+    fn test_accelerate_to_max_speed() {
+        let movement = movement();
+        let mut velocity = Vec2::ZERO;
+        for want in [10.0, 20.0, 30.0, 40.0, 50.0, 50.0] {
+            velocity = movement.step(velocity, Vec2::X, DT);
+            assert!((velocity.x - want).abs() < 1e-4, "{velocity} != {want}");
+            assert_eq!(velocity.y, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_input_is_clamped() {
+        let movement = movement();
+        let mut velocity = Vec2::ZERO;
+        for _ in 0..20 {
+            velocity = movement.step(velocity, vec2(3.0, 4.0), DT);
+        }
+        assert!((velocity.length() - movement.speed).abs() < 1e-4);
+        assert!((velocity.normalize() - vec2(0.6, 0.8)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_decelerate_to_zero_without_overshoot() {
+        let movement = movement();
+        let mut velocity = vec2(50.0, 0.0);
+        for want in [30.0, 10.0, 0.0, 0.0] {
+            velocity = movement.step(velocity, Vec2::ZERO, DT);
+            assert!((velocity.x - want).abs() < 1e-4, "{velocity} != {want}");
+        }
+    }
+
+    #[test]
+    fn test_decelerate_along_velocity() {
+        let movement = movement();
+        let velocity = movement.step(vec2(30.0, 40.0), Vec2::ZERO, DT);
+        assert!((velocity - vec2(18.0, 24.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_reverse_direction() {
+        let movement = movement();
+        let velocity = movement.step(vec2(50.0, 0.0), Vec2::NEG_X, DT);
+        assert!((velocity.x - 40.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_impulse_decays_independently() {
+        let movement = movement();
+        let mut external = ExternalVelocity {
+            decay: 500.0,
+            ..default()
+        };
+        external.apply_impulse(vec2(0.0, 100.0));
+
+        let mut self_velocity = Vec2::ZERO;
+        self_velocity = movement.step(self_velocity, Vec2::X, DT);
+        external.step(DT);
+        assert!((self_velocity + external.total() - vec2(10.0, 50.0)).length() < 1e-4);
+
+        external.step(DT);
+        assert_eq!(external.impulse, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_carry_is_added() {
+        let external = ExternalVelocity {
+            impulse: vec2(1.0, 0.0),
+            carry: vec2(0.0, 2.0),
+            ..default()
+        };
+        assert_eq!(external.total(), vec2(1.0, 2.0));
+    }
+}
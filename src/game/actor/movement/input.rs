@@ -1,19 +1,33 @@
 use crate::game::actor::ActorAssets;
-use crate::game::actor::movement::MovementController;
+use crate::game::actor::animation::AnimationController;
+use crate::game::actor::animation::AnimationState;
+use crate::game::actor::camera_cutie::CameraSequence;
+use crate::game::actor::combat::health::Downed;
+use crate::game::actor::enemy::Enemy;
+use crate::game::actor::facing::Facing;
+use crate::game::actor::movement::{ExternalVelocity, MovementController};
 use crate::game::actor::player::{Player, get_player_projectile};
+use crate::game::item::effects::fire::AppliesFire;
+use crate::game::item::effects::poison::AppliesPoison;
+use crate::game::settings::{AccessibilitySettings, GameplaySettings};
+use crate::game::sfx::{PlaySfx, SfxId};
 use crate::prelude::*;
 use std::f32::consts::PI;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<PlayerAction>();
+    app.configure::<(PlayerAction, ShotCooldown, Dash)>();
 }
 
 #[derive(Actionlike, Eq, PartialEq, Hash, Copy, Clone, Reflect, Debug)]
 pub(crate) enum PlayerAction {
     #[actionlike(DualAxis)]
     Move,
+    #[actionlike(DualAxis)]
+    Aim,
     Shoot,
     Dash,
+    Interact,
+    Skip,
 }
 
 impl Configure for PlayerAction {
@@ -25,11 +39,10 @@ impl Configure for PlayerAction {
             (
                 record_movement_action
                     .in_set(UpdateSystems::RecordInput)
-                    .run_if(Pause::is_disabled),
+                    .run_if(Pause::is_disabled.and(CameraSequence::is_inactive)),
                 spawn_projectile
                     .in_set(UpdateSystems::RecordInput)
-                    //.run_if(action_just_pressed(Action::Shoot)) not working for some reason don't care why
-                    .run_if(input_just_pressed(MouseButton::Left)),
+                    .run_if(Pause::is_disabled.and(CameraSequence::is_inactive)),
             ),
         );
         app.add_observer(despawn_shot_on_collision);
@@ -37,7 +50,7 @@ impl Configure for PlayerAction {
 }
 
 fn record_movement_action(
-    mut action_query: Query<(&ActionState<PlayerAction>, &mut MovementController)>,
+    mut action_query: Query<(&ActionState<PlayerAction>, &mut MovementController), Without<Downed>>,
 ) {
     for (action, mut controller) in &mut action_query {
         controller.0 += action
@@ -47,20 +60,108 @@ fn record_movement_action(
     }
 }
 
+/// The seconds between shots while auto-fire is held.
+const AUTO_FIRE_INTERVAL_SECS: f32 = 0.25;
+
+/// The cooldown between a player's shots while holding [`PlayerAction::Shoot`] with auto-fire.
+///
+/// Without auto-fire, every press fires immediately.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ShotCooldown {
+    /// Seconds between shots.
+    pub interval: f32,
+    /// Seconds until the next shot.
+    pub remaining: f32,
+}
+
+impl Configure for ShotCooldown {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            tick_shot_cooldown
+                .in_set(UpdateSystems::TickTimers)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+impl Default for ShotCooldown {
+    fn default() -> Self {
+        Self {
+            interval: AUTO_FIRE_INTERVAL_SECS,
+            remaining: 0.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn tick_shot_cooldown(time: Res<Time>, mut cooldown_query: Query<&mut ShotCooldown>) {
+    let dt = time.delta_secs();
+    for mut cooldown in &mut cooldown_query {
+        cq!(cooldown.remaining > 0.0);
+        cooldown.remaining = (cooldown.remaining - dt).max(0.0);
+    }
+}
+
+/// The maximum angle in radians that aim assist will correct a shot by.
+const AIM_ASSIST_ANGLE: f32 = 0.25;
+
+/// The maximum distance to an enemy that aim assist will target.
+const AIM_ASSIST_RANGE: f32 = 400.0;
+
 fn spawn_projectile(
     mut commands: Commands,
-    window_query: Query<&Window>,
-    player_query: Query<&Position, With<Player>>,
+    gameplay_settings: Res<GameplaySettings>,
+    accessibility_settings: Res<AccessibilitySettings>,
+    enemy_query: Query<&Position, (With<Enemy>, Without<Player>)>,
+    mut player_query: Query<
+        (
+            &ActionState<PlayerAction>,
+            &Facing,
+            &Position,
+            &AppliesFire,
+            &AppliesPoison,
+            &mut ShotCooldown,
+            &mut AnimationController,
+        ),
+        (With<Player>, Without<Downed>),
+    >,
     assets: Res<ActorAssets>,
 ) {
-    window_query.iter().for_each(|window| {
-        let mouse_position = rq!(window.cursor_position());
-        let window_center = vec2(window.width() / 2.0, window.height() / 2.0);
+    for (action, facing, player_position, fire, poison, mut cooldown, mut controller) in
+        &mut player_query
+    {
+        if accessibility_settings.auto_fire {
+            cq!(action.pressed(&PlayerAction::Shoot));
+            cq!(cooldown.remaining <= 0.0);
+            cooldown.remaining = cooldown.interval;
+        } else {
+            cq!(action.just_pressed(&PlayerAction::Shoot));
+        }
+        controller.play(AnimationState::Attack);
 
-        let trajectory = mouse_position - window_center;
-        let clamped_traj = trajectory.clamp_length_max(1.0).normalize();
-        let player_position = r!(player_query.single());
+        // Aim assist only applies to stick aiming, since the mouse is already precise.
+        let mut direction = facing.0.as_vec2();
+        if gameplay_settings.aim_assist && action.axis_pair(&PlayerAction::Aim) != Vec2::ZERO {
+            if let Some(assisted) = enemy_query
+                .iter()
+                .map(|enemy_position| enemy_position.0 - player_position.0)
+                .filter(|offset| offset.length() <= AIM_ASSIST_RANGE)
+                .filter(|offset| offset.angle_to(direction).abs() <= AIM_ASSIST_ANGLE)
+                .min_by(|a, b| {
+                    a.angle_to(direction)
+                        .abs()
+                        .total_cmp(&b.angle_to(direction).abs())
+                })
+            {
+                direction = assisted.normalize_or(direction);
+            }
+        }
 
+        // Shoot where the player is aiming, in screen space (y down).
+        let trajectory = vec2(direction.x, -direction.y);
         let bounded_angle = f32::atan(trajectory.x / trajectory.y);
         let angle = if trajectory.y > 0.0 {
             bounded_angle + PI
@@ -68,13 +169,89 @@ fn spawn_projectile(
             bounded_angle
         };
 
+        commands.trigger(PlaySfx::new(SfxId::Shoot).at(player_position.0));
         commands.spawn(get_player_projectile(
             assets.projectile_image.clone(),
-            clamped_traj,
+            trajectory,
             angle,
             player_position.0,
+            *fire,
+            *poison,
         ));
-    });
+    }
+}
+
+/// A quick burst of speed in the movement direction, with a cooldown.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(ExternalVelocity)]
+pub struct Dash {
+    /// The speed of the burst in pixels per second.
+    pub speed: f32,
+    /// Seconds between dashes.
+    pub cooldown: f32,
+    /// Seconds until the next dash.
+    pub remaining: f32,
+}
+
+impl Configure for Dash {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                tick_dash.in_set(UpdateSystems::TickTimers),
+                dash.in_set(UpdateSystems::RecordInput)
+                    .run_if(CameraSequence::is_inactive),
+            )
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            speed: 800.0,
+            cooldown: 1.0,
+            remaining: 0.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn tick_dash(time: Res<Time>, mut dash_query: Query<&mut Dash>) {
+    let dt = time.delta_secs();
+    for mut dash in &mut dash_query {
+        cq!(dash.remaining > 0.0);
+        dash.remaining = (dash.remaining - dt).max(0.0);
+    }
+}
+
+/// Dash in the movement direction, or the facing direction if standing still.
+#[cfg_attr(feature = "native_dev", hot)]
+fn dash(
+    mut commands: Commands,
+    mut dash_query: Query<
+        (
+            &ActionState<PlayerAction>,
+            &Facing,
+            &Position,
+            &mut Dash,
+            &mut ExternalVelocity,
+        ),
+        Without<Downed>,
+    >,
+) {
+    for (action, facing, position, mut dash, mut velocity) in &mut dash_query {
+        cq!(action.just_pressed(&PlayerAction::Dash));
+        cq!(dash.remaining <= 0.0);
+        dash.remaining = dash.cooldown;
+
+        let direction = Dir2::new(action.axis_pair(&PlayerAction::Move)).unwrap_or(facing.0);
+        velocity.apply_impulse(dash.speed * direction);
+        commands.trigger(PlaySfx::new(SfxId::Dash).at(position.0));
+    }
 }
 
 fn despawn_shot_on_collision(
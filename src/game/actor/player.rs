@@ -1,25 +1,213 @@
 use super::movement::input::PlayerAction;
+use crate::core::camera::OnCameraShake;
 use crate::game::GameLayer;
-use crate::game::actor::combat::damage::Damage;
-use crate::game::actor::combat::health::Health;
+use crate::game::actor::ActorAssets;
+use crate::game::actor::combat::damage::{Damage, Knockback, OnDamage};
+use crate::game::actor::combat::heal::OnHeal;
+use crate::game::actor::combat::health::{Downed, Health, OnDowned, OnRevived, Revivable};
+use crate::game::actor::combat::shield::Shield;
 use crate::game::actor::create_entity_aseprite;
+use crate::game::actor::experience::Experience;
+use crate::game::actor::facing::FacingSource;
+use crate::game::actor::movement::input::{Dash, ShotCooldown};
 use crate::game::actor::movement::{Movement, MovementController};
+use crate::game::controls::{ControlSlot, ControlsSettings};
+use crate::game::economy::Wallet;
+use crate::game::interact::Interactor;
 use crate::game::item::effects::fire::AppliesFire;
 use crate::game::item::effects::poison::AppliesPoison;
+use crate::game::item::pool::Inventory;
+use crate::game::room::OnRoomClear;
 use crate::game::world::Level;
 use crate::prelude::*;
+use crate::screen::Screen;
+use crate::screen::fade::fade_out;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure::<Player>();
 }
 
+/// A local player, numbered from 0.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-pub struct Player;
+pub struct Player {
+    pub index: usize,
+}
 
 impl Configure for Player {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                reconnect_gamepad,
+                claim_gamepad,
+                join_player.run_if(Pause::is_disabled),
+            )
+                .chain()
+                .in_set(UpdateSystems::RecordInput),
+        );
+        app.add_observer(shake_camera_on_damage);
+        app.add_observer(fade_downed_player);
+        app.add_observer(unfade_revived_player);
+        app.add_observer(end_game_when_all_downed);
+        app.add_observer(revive_players_on_room_clear);
+    }
+}
+
+/// The number of local players supported.
+const MAX_PLAYERS: usize = 2;
+
+/// The fraction of max health a downed player revives with.
+const REVIVE_HEALTH: f32 = 0.3;
+
+/// Give a newly connected gamepad to the first player whose gamepad is disconnected.
+#[cfg_attr(feature = "native_dev", hot)]
+fn reconnect_gamepad(
+    new_gamepad_query: Query<Entity, Added<Gamepad>>,
+    gamepad_query: Query<(), With<Gamepad>>,
+    mut player_query: Query<(&Player, &mut InputMap<PlayerAction>)>,
+) {
+    for gamepad in &new_gamepad_query {
+        cq!(!player_query
+            .iter()
+            .any(|(_, input_map)| input_map.gamepad() == Some(gamepad)));
+        let (_, mut input_map) = cq!(player_query
+            .iter_mut()
+            .filter(|(_, input_map)| {
+                input_map
+                    .gamepad()
+                    .is_some_and(|x| !gamepad_query.contains(x))
+            })
+            .min_by_key(|(player, _)| player.index));
+        input_map.set_gamepad(gamepad);
+    }
+}
+
+/// Give an unclaimed gamepad to the first player once they use it.
+///
+/// Until then, the first player reads input from any gamepad.
+#[cfg_attr(feature = "native_dev", hot)]
+fn claim_gamepad(
+    gamepad_query: Query<(Entity, &Gamepad)>,
+    mut player_query: Query<(&Player, &mut InputMap<PlayerAction>)>,
+) {
+    let claimed = player_query
+        .iter()
+        .filter_map(|(_, input_map)| input_map.gamepad())
+        .collect::<Vec<_>>();
+    let (_, mut input_map) = rq!(player_query
+        .iter_mut()
+        .find(|(player, input_map)| player.index == 0 && input_map.gamepad().is_none()));
+    // Pressing Select on an unclaimed gamepad joins a new player instead.
+    let gamepad = rq!(gamepad_query
+        .iter()
+        .filter(|(entity, _)| !claimed.contains(entity))
+        .find(|(_, gamepad)| {
+            gamepad
+                .get_just_pressed()
+                .any(|&button| button != GamepadButton::Select)
+                || gamepad.left_stick().length() > 0.5
+                || gamepad.right_stick().length() > 0.5
+        })
+        .map(|(entity, _)| entity));
+    input_map.set_gamepad(gamepad);
+}
+
+/// Join as a new player by pressing Select on an unclaimed gamepad.
+#[cfg_attr(feature = "native_dev", hot)]
+fn join_player(
+    mut commands: Commands,
+    actor_assets: Res<ActorAssets>,
+    controls: Res<ControlsSettings>,
+    gamepad_query: Query<(Entity, &Gamepad)>,
+    mut player_query: Query<(&Player, &mut InputMap<PlayerAction>, &Transform)>,
+) {
+    let player_count = player_query.iter().count();
+    rq!(player_count > 0 && player_count < MAX_PLAYERS);
+
+    let claimed = player_query
+        .iter()
+        .filter_map(|(_, input_map, _)| input_map.gamepad())
+        .collect::<Vec<_>>();
+    let gamepad = rq!(gamepad_query
+        .iter()
+        .filter(|(entity, _)| !claimed.contains(entity))
+        .find(|(_, gamepad)| gamepad.just_pressed(GamepadButton::Select))
+        .map(|(entity, _)| entity));
+
+    let mut spawn_pos = Vec3::ZERO;
+    for (player, mut input_map, transform) in &mut player_query {
+        cq!(player.index == 0);
+        spawn_pos = transform.translation + vec3(48.0, 0.0, 0.0);
+        // Stop a keyboard-only first player from reading the new player's gamepad.
+        // `reconnect_gamepad` will still give them the next gamepad that connects.
+        if input_map.gamepad().is_none() {
+            input_map.set_gamepad(Entity::PLACEHOLDER);
+        }
+    }
+
+    commands.spawn((
+        get_player(
+            actor_assets.rat_handle.clone(),
+            &controls,
+            player_count,
+            Some(gamepad),
+        ),
+        Transform::from_translation(spawn_pos),
+        DespawnOnExitState::<Level>::default(),
+    ));
+}
+
+fn shake_camera_on_damage(
+    trigger: Trigger<OnDamage>,
+    mut commands: Commands,
+    player_query: Query<(), With<Player>>,
+) {
+    let target = r!(trigger.get_target());
+    rq!(player_query.contains(target));
+    // Damage-over-time ticks would keep the camera shaking constantly.
+    rq!(!trigger.is_tick);
+
+    commands.trigger(OnCameraShake((trigger.damage / 40.0).clamp(0.2, 0.6)));
+}
+
+fn fade_downed_player(
+    trigger: Trigger<OnDowned>,
+    mut sprite_query: Query<&mut Sprite, With<Player>>,
+) {
+    let target = r!(trigger.get_target());
+    let mut sprite = rq!(sprite_query.get_mut(target));
+    sprite.color = sprite.color.with_alpha(0.4);
+}
+
+fn unfade_revived_player(
+    trigger: Trigger<OnRevived>,
+    mut sprite_query: Query<&mut Sprite, With<Player>>,
+) {
+    let target = r!(trigger.get_target());
+    let mut sprite = rq!(sprite_query.get_mut(target));
+    sprite.color = sprite.color.with_alpha(1.0);
+}
+
+fn end_game_when_all_downed(
+    _: Trigger<OnDowned>,
+    mut commands: Commands,
+    player_query: Query<Has<Downed>, With<Player>>,
+) {
+    rq!(player_query.iter().all(|downed| downed));
+    commands.spawn(fade_out(Screen::Title));
+}
+
+fn revive_players_on_room_clear(
+    _: Trigger<OnRoomClear>,
+    mut commands: Commands,
+    player_query: Query<Entity, (With<Player>, With<Downed>)>,
+) {
+    for entity in &player_query {
+        commands
+            .entity(entity)
+            .trigger(OnHeal::percent(REVIVE_HEALTH));
     }
 }
 
@@ -36,26 +224,45 @@ const ACCELERATION_RATE_PIXELS: f32 = ACCELERATION_RATE_FEET * 12.0;
 const DECELERATION_RATE_FEET: f32 = 150.0;
 const DECELERATION_RATE_PIXELS: f32 = DECELERATION_RATE_FEET * 12.0;
 
-pub fn get_player(texture: Handle<Aseprite>) -> impl Bundle {
+pub fn get_player(
+    texture: Handle<Aseprite>,
+    controls: &ControlsSettings,
+    index: usize,
+    gamepad: Option<Entity>,
+) -> impl Bundle {
     (
-        Name::new("Player"),
-        Player,
+        Name::new(format!("Player {}", index + 1)),
+        (Player { index }, Revivable),
         Health {
             max: 500.,
             current: 100.,
         },
+        Shield::new(50.).with_regen(3., 10.),
         Movement::new(
             ACCELERATION_RATE_PIXELS,
             DECELERATION_RATE_PIXELS,
             WALKING_SPEED_PIXELS_PER_SECOND,
             1.0,
         ),
-        MovementController::default(),
-        InputMap::default()
-            .with_dual_axis(PlayerAction::Move, GamepadStick::LEFT)
-            .with_dual_axis(PlayerAction::Move, VirtualDPad::wasd())
-            .with(PlayerAction::Shoot, MouseButton::Left)
-            .with(PlayerAction::Dash, KeyCode::ShiftLeft),
+        (
+            MovementController::default(),
+            Dash::default(),
+            ShotCooldown::default(),
+        ),
+        // Only the first player has a mouse to fall back on.
+        if index == 0 {
+            FacingSource::Aim
+        } else {
+            FacingSource::AimStick
+        },
+        Interactor::default(),
+        (Wallet::default(), Experience::default()),
+        (
+            Inventory::default(),
+            AppliesFire::new(0.5),
+            AppliesPoison::new(0.2),
+        ),
+        player_input_map(controls, index, gamepad),
         children![(
             Name::new("Player Collider"),
             CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
@@ -64,15 +271,36 @@ pub fn get_player(texture: Handle<Aseprite>) -> impl Bundle {
             ColliderDensity(5.0),
             CollisionEventsEnabled,
         )],
-        create_entity_aseprite(texture, "Idle"),
+        create_entity_aseprite(texture, "rat"),
     )
 }
 
+/// Every player can use a gamepad, and the first player can also use keyboard and mouse.
+pub(crate) fn player_input_map(
+    controls: &ControlsSettings,
+    index: usize,
+    gamepad: Option<Entity>,
+) -> InputMap<PlayerAction> {
+    let mut input_map = InputMap::default()
+        .with_dual_axis(PlayerAction::Move, GamepadStick::LEFT)
+        .with_dual_axis(PlayerAction::Aim, GamepadStick::RIGHT);
+    controls.insert_player_bindings(&mut input_map, ControlSlot::Gamepad);
+    if index == 0 {
+        controls.insert_player_bindings(&mut input_map, ControlSlot::Keyboard);
+    }
+    if let Some(gamepad) = gamepad {
+        input_map.set_gamepad(gamepad);
+    }
+    input_map
+}
+
 pub fn get_player_projectile(
     sprite: Handle<Aseprite>,
     trajectory: Vec2,
     angle: f32,
     player_offset: Vec2,
+    fire: AppliesFire,
+    poison: AppliesPoison,
 ) -> impl Bundle {
     (
         Name::new("Projectile"),
@@ -96,8 +324,9 @@ pub fn get_player_projectile(
         Collider::capsule(5.0, 5.0),
         CollisionLayers::new(GameLayer::Projectile, LayerMask::ALL),
         CollisionEventsEnabled,
-        AppliesFire::new(0.5),
-        AppliesPoison::new(0.2),
+        Knockback(150.0),
+        fire,
+        poison,
         DespawnOnExitState::<Level>::Recursive,
     )
 }
@@ -0,0 +1,435 @@
+//! Rebindable controls.
+
+use crate::game::actor::movement::input::PlayerAction;
+use crate::game::actor::player::Player;
+use crate::game::actor::player::player_input_map;
+use crate::prelude::*;
+use crate::screen::gameplay::GameplayAction;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<ControlsSettings>();
+}
+
+/// An action that can be rebound in the controls menu.
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ControlAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Shoot,
+    Dash,
+    Interact,
+    Skip,
+    Pause,
+}
+
+impl ControlAction {
+    pub const MOVEMENT: [Self; 4] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+    ];
+    pub const BUTTONS: [Self; 5] = [
+        Self::Shoot,
+        Self::Dash,
+        Self::Interact,
+        Self::Skip,
+        Self::Pause,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MoveUp => "Move up",
+            Self::MoveDown => "Move down",
+            Self::MoveLeft => "Move left",
+            Self::MoveRight => "Move right",
+            Self::Shoot => "Shoot",
+            Self::Dash => "Dash",
+            Self::Interact => "Interact",
+            Self::Skip => "Skip",
+            Self::Pause => "Pause",
+        }
+    }
+
+    /// Whether this action can be bound to the given input.
+    ///
+    /// Movement is a [`VirtualDPad`], so it can't mix keys and mouse buttons.
+    pub fn accepts(self, binding: InputBinding) -> bool {
+        !(Self::MOVEMENT.contains(&self) && matches!(binding, InputBinding::Mouse(_)))
+    }
+
+    fn player_action(self) -> Option<PlayerAction> {
+        Some(match self {
+            Self::Shoot => PlayerAction::Shoot,
+            Self::Dash => PlayerAction::Dash,
+            Self::Interact => PlayerAction::Interact,
+            Self::Skip => PlayerAction::Skip,
+            _ => return None,
+        })
+    }
+}
+
+/// The input device a binding belongs to.
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub enum ControlSlot {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    pub fn slot(self) -> ControlSlot {
+        match self {
+            Self::Key(_) | Self::Mouse(_) => ControlSlot::Keyboard,
+            Self::Gamepad(_) => ControlSlot::Gamepad,
+        }
+    }
+
+    /// A short name to display in the controls menu.
+    pub fn label(self) -> String {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                let name = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name);
+                name.to_string()
+            },
+            Self::Mouse(MouseButton::Left) => "LMB".to_string(),
+            Self::Mouse(MouseButton::Right) => "RMB".to_string(),
+            Self::Mouse(MouseButton::Middle) => "MMB".to_string(),
+            Self::Mouse(button) => format!("Mouse {button:?}"),
+            Self::Gamepad(button) => match button {
+                GamepadButton::LeftTrigger => "LB",
+                GamepadButton::RightTrigger => "RB",
+                GamepadButton::LeftTrigger2 => "LT",
+                GamepadButton::RightTrigger2 => "RT",
+                GamepadButton::LeftThumb => "L3",
+                GamepadButton::RightThumb => "R3",
+                GamepadButton::DPadUp => "D-pad up",
+                GamepadButton::DPadDown => "D-pad down",
+                GamepadButton::DPadLeft => "D-pad left",
+                GamepadButton::DPadRight => "D-pad right",
+                _ => return format!("{button:?}"),
+            }
+            .to_string(),
+        }
+    }
+
+    /// Whether this is one of the bindings for `action` in `input_map`.
+    pub fn is_bound_to<A: Actionlike>(self, input_map: &InputMap<A>, action: &A) -> bool {
+        let binding: Box<dyn Buttonlike> = match self {
+            Self::Key(key) => Box::new(key),
+            Self::Mouse(button) => Box::new(button),
+            Self::Gamepad(button) => Box::new(button),
+        };
+        input_map
+            .get_buttonlike(action)
+            .is_some_and(|bindings| bindings.contains(&binding))
+    }
+
+    fn insert<A: Actionlike>(self, input_map: &mut InputMap<A>, action: A) {
+        match self {
+            Self::Key(key) => input_map.insert(action, key),
+            Self::Mouse(button) => input_map.insert(action, button),
+            Self::Gamepad(button) => input_map.insert(action, button),
+        };
+    }
+}
+
+/// The result of [`ControlsSettings::bind`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BindOutcome {
+    /// The input wasn't used by another action.
+    Bound,
+    /// The other action that used the input now has the replaced binding.
+    Swapped(ControlAction),
+    /// The other action that used the input is now unbound.
+    Unbound(ControlAction),
+    /// The input is still bound to a movement direction, and nothing changed.
+    Refused(ControlAction),
+}
+
+impl BindOutcome {
+    /// A message to show in the controls menu.
+    pub fn message(self) -> String {
+        match self {
+            Self::Bound => String::new(),
+            Self::Swapped(other) => format!("Swapped with {}", other.label()),
+            Self::Unbound(other) => format!("Unbound {}", other.label()),
+            Self::Refused(other) => format!("Already used by {}", other.label()),
+        }
+    }
+}
+
+/// The player's bindings for each [`ControlAction`], by input device.
+///
+/// Gamepad sticks (movement and aiming) and the `P` pause key are not rebindable.
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct ControlsSettings {
+    pub keyboard: HashMap<ControlAction, InputBinding>,
+    pub gamepad: HashMap<ControlAction, InputBinding>,
+}
+
+impl Configure for ControlsSettings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            apply_controls_settings
+                .run_if(resource_changed::<Self>)
+                .in_set(UpdateSystems::SyncEarly),
+        );
+    }
+}
+
+impl Default for ControlsSettings {
+    fn default() -> Self {
+        use ControlAction::*;
+        use InputBinding::*;
+
+        Self {
+            keyboard: HashMap::from([
+                (MoveUp, Key(KeyCode::KeyW)),
+                (MoveDown, Key(KeyCode::KeyS)),
+                (MoveLeft, Key(KeyCode::KeyA)),
+                (MoveRight, Key(KeyCode::KeyD)),
+                (Shoot, Mouse(MouseButton::Left)),
+                (Dash, Key(KeyCode::ShiftLeft)),
+                (Interact, Key(KeyCode::KeyE)),
+                (Skip, Key(KeyCode::Space)),
+                (Pause, Key(KeyCode::Escape)),
+            ]),
+            gamepad: HashMap::from([
+                (MoveUp, Gamepad(GamepadButton::DPadUp)),
+                (MoveDown, Gamepad(GamepadButton::DPadDown)),
+                (MoveLeft, Gamepad(GamepadButton::DPadLeft)),
+                (MoveRight, Gamepad(GamepadButton::DPadRight)),
+                (Shoot, Gamepad(GamepadButton::RightTrigger2)),
+                (Dash, Gamepad(GamepadButton::RightTrigger)),
+                (Interact, Gamepad(GamepadButton::West)),
+                (Skip, Gamepad(GamepadButton::South)),
+                (Pause, Gamepad(GamepadButton::Start)),
+            ]),
+        }
+    }
+}
+
+impl ControlsSettings {
+    pub fn slot(&self, slot: ControlSlot) -> &HashMap<ControlAction, InputBinding> {
+        match slot {
+            ControlSlot::Keyboard => &self.keyboard,
+            ControlSlot::Gamepad => &self.gamepad,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: ControlSlot) -> &mut HashMap<ControlAction, InputBinding> {
+        match slot {
+            ControlSlot::Keyboard => &mut self.keyboard,
+            ControlSlot::Gamepad => &mut self.gamepad,
+        }
+    }
+
+    pub fn get(&self, action: ControlAction, slot: ControlSlot) -> Option<InputBinding> {
+        self.slot(slot).get(&action).copied()
+    }
+
+    /// Bind an input to an action, swapping bindings with any action that already uses it.
+    ///
+    /// Refuses to take the input from a movement direction that would be left unbound.
+    pub fn bind(&mut self, action: ControlAction, binding: InputBinding) -> BindOutcome {
+        let bindings = self.slot_mut(binding.slot());
+        let conflict = bindings
+            .iter()
+            .find(|&(&other, &other_binding)| other != action && other_binding == binding)
+            .map(|(&other, _)| other);
+        let conflict = match conflict {
+            Some(conflict) => conflict,
+            None => {
+                bindings.insert(action, binding);
+                return BindOutcome::Bound;
+            },
+        };
+
+        // The conflicting action can't always accept the replaced binding.
+        let previous = bindings
+            .get(&action)
+            .copied()
+            .filter(|&previous| conflict.accepts(previous));
+        match previous {
+            Some(previous) => {
+                bindings.insert(action, binding);
+                bindings.insert(conflict, previous);
+                BindOutcome::Swapped(conflict)
+            },
+            // Movement needs all four directions bound, so keep the conflict's binding.
+            None if ControlAction::MOVEMENT.contains(&conflict) => BindOutcome::Refused(conflict),
+            None => {
+                bindings.insert(action, binding);
+                bindings.remove(&conflict);
+                BindOutcome::Unbound(conflict)
+            },
+        }
+    }
+
+    /// Add the bindings for one input device to a player's input map.
+    pub(crate) fn insert_player_bindings(
+        &self,
+        input_map: &mut InputMap<PlayerAction>,
+        slot: ControlSlot,
+    ) {
+        let bindings = self.slot(slot);
+        let [up, down, left, right] = ControlAction::MOVEMENT.map(|action| bindings.get(&action));
+        match (up, down, left, right) {
+            (
+                Some(&InputBinding::Key(up)),
+                Some(&InputBinding::Key(down)),
+                Some(&InputBinding::Key(left)),
+                Some(&InputBinding::Key(right)),
+            ) => {
+                input_map
+                    .insert_dual_axis(PlayerAction::Move, VirtualDPad::new(up, down, left, right));
+            },
+            (
+                Some(&InputBinding::Gamepad(up)),
+                Some(&InputBinding::Gamepad(down)),
+                Some(&InputBinding::Gamepad(left)),
+                Some(&InputBinding::Gamepad(right)),
+            ) => {
+                input_map
+                    .insert_dual_axis(PlayerAction::Move, VirtualDPad::new(up, down, left, right));
+            },
+            _ => {},
+        }
+
+        for action in ControlAction::BUTTONS {
+            let player_action = cq!(action.player_action());
+            let binding = cq!(bindings.get(&action));
+            binding.insert(input_map, player_action);
+        }
+    }
+
+    pub fn gameplay_input_map(&self) -> InputMap<GameplayAction> {
+        let mut input_map = InputMap::default()
+            .with(GameplayAction::Pause, KeyCode::KeyP)
+            .with(GameplayAction::CloseMenu, KeyCode::KeyP);
+        for slot in [ControlSlot::Keyboard, ControlSlot::Gamepad] {
+            let binding = cq!(self.get(ControlAction::Pause, slot));
+            binding.insert(&mut input_map, GameplayAction::Pause);
+        }
+        input_map
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_controls_settings(
+    controls: Res<ControlsSettings>,
+    mut gameplay_input_map: ResMut<InputMap<GameplayAction>>,
+    mut player_query: Query<(&Player, &mut InputMap<PlayerAction>)>,
+) {
+    *gameplay_input_map = controls.gameplay_input_map();
+    for (player, mut input_map) in &mut player_query {
+        let gamepad = input_map.gamepad();
+        *input_map = player_input_map(&controls, player.index, gamepad);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_swaps_with_conflict() {
+        let mut controls = ControlsSettings::default();
+        let outcome = controls.bind(ControlAction::Dash, InputBinding::Key(KeyCode::KeyE));
+        assert_eq!(outcome, BindOutcome::Swapped(ControlAction::Interact));
+        assert_eq!(
+            controls.get(ControlAction::Dash, ControlSlot::Keyboard),
+            Some(InputBinding::Key(KeyCode::KeyE)),
+        );
+        assert_eq!(
+            controls.get(ControlAction::Interact, ControlSlot::Keyboard),
+            Some(InputBinding::Key(KeyCode::ShiftLeft)),
+        );
+    }
+
+    #[test]
+    fn test_bind_unbinds_conflict_without_previous_binding() {
+        let mut controls = ControlsSettings::default();
+        controls.gamepad.remove(&ControlAction::Dash);
+        let south = InputBinding::Gamepad(GamepadButton::South);
+        let outcome = controls.bind(ControlAction::Dash, south);
+        assert_eq!(outcome, BindOutcome::Unbound(ControlAction::Skip));
+        assert_eq!(
+            controls.get(ControlAction::Dash, ControlSlot::Gamepad),
+            Some(south),
+        );
+        assert_eq!(
+            controls.get(ControlAction::Skip, ControlSlot::Gamepad),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bind_never_unbinds_movement() {
+        let mut controls = ControlsSettings::default();
+        let w = InputBinding::Key(KeyCode::KeyW);
+        let outcome = controls.bind(ControlAction::Shoot, w);
+        assert_eq!(outcome, BindOutcome::Refused(ControlAction::MoveUp));
+        assert_eq!(
+            controls.get(ControlAction::Shoot, ControlSlot::Keyboard),
+            Some(InputBinding::Mouse(MouseButton::Left)),
+        );
+        assert_eq!(
+            controls.get(ControlAction::MoveUp, ControlSlot::Keyboard),
+            Some(w)
+        );
+    }
+
+    #[test]
+    fn test_bind_swaps_with_movement() {
+        let mut controls = ControlsSettings::default();
+        let w = InputBinding::Key(KeyCode::KeyW);
+        let outcome = controls.bind(ControlAction::Interact, w);
+        assert_eq!(outcome, BindOutcome::Swapped(ControlAction::MoveUp));
+        assert_eq!(
+            controls.get(ControlAction::MoveUp, ControlSlot::Keyboard),
+            Some(InputBinding::Key(KeyCode::KeyE)),
+        );
+    }
+
+    #[test]
+    fn test_bind_without_conflict() {
+        let mut controls = ControlsSettings::default();
+        let f = InputBinding::Key(KeyCode::KeyF);
+        assert_eq!(
+            controls.bind(ControlAction::Interact, f),
+            BindOutcome::Bound
+        );
+        assert_eq!(
+            controls.get(ControlAction::Interact, ControlSlot::Keyboard),
+            Some(f)
+        );
+    }
+
+    #[test]
+    fn test_movement_rejects_mouse() {
+        let mouse = InputBinding::Mouse(MouseButton::Right);
+        assert!(!ControlAction::MoveLeft.accepts(mouse));
+        assert!(ControlAction::Shoot.accepts(mouse));
+        assert!(ControlAction::MoveLeft.accepts(InputBinding::Key(KeyCode::KeyJ)));
+    }
+}
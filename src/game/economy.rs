@@ -0,0 +1,114 @@
+pub mod shop;
+
+use crate::game::GameLayer;
+use crate::game::actor::combat::health::OnDeath;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Wallet, DropsCurrency, CurrencyPickup)>();
+    app.add_plugins(shop::plugin);
+}
+
+/// The currency an actor is carrying.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Wallet(pub u32);
+
+impl Configure for Wallet {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Wallet {
+    /// Deduct `amount` if there is enough currency, returning whether it succeeded.
+    pub fn spend(&mut self, amount: u32) -> bool {
+        let Some(remaining) = self.0.checked_sub(amount) else {
+            return false;
+        };
+        self.0 = remaining;
+        true
+    }
+}
+
+/// Drops a random amount of currency on death.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DropsCurrency {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Configure for DropsCurrency {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(drop_currency_on_death);
+    }
+}
+
+impl Default for DropsCurrency {
+    fn default() -> Self {
+        Self { min: 1, max: 3 }
+    }
+}
+
+fn drop_currency_on_death(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    drop_query: Query<(&DropsCurrency, &GlobalTransform)>,
+) {
+    let target = r!(trigger.get_target());
+    let (drop, gt) = rq!(drop_query.get(target));
+    let amount = thread_rng().gen_range(drop.min..=drop.max.max(drop.min));
+    rq!(amount > 0);
+
+    commands.spawn((
+        currency_pickup(amount),
+        Transform::from_translation(gt.translation().xy().extend(4.0)),
+        DespawnOnExitState::<Level>::default(),
+    ));
+}
+
+/// Currency lying in the world, waiting to be collected.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CurrencyPickup(pub u32);
+
+impl Configure for CurrencyPickup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(collect_currency);
+    }
+}
+
+fn collect_currency(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    pickup_query: Query<&CurrencyPickup>,
+    mut wallet_query: Query<&mut Wallet>,
+) {
+    let pickup_entity = r!(trigger.get_target());
+    let pickup = rq!(pickup_query.get(pickup_entity));
+    let body = rq!(trigger.body);
+    let mut wallet = rq!(wallet_query.get_mut(body));
+
+    wallet.0 += pickup.0;
+    commands
+        .entity(body)
+        .trigger(PlaySfx::new(SfxId::Pickup).with_pitch_variance(0.25));
+    commands.entity(pickup_entity).despawn();
+}
+
+pub fn currency_pickup(amount: u32) -> impl Bundle {
+    (
+        Name::new("Currency"),
+        CurrencyPickup(amount),
+        Sprite::from_color(Color::Srgba(YELLOW_400), Vec2::splat(6.0)),
+        Collider::circle(6.0),
+        Sensor,
+        CollisionEventsEnabled,
+        CollisionLayers::new(GameLayer::Pickup, GameLayer::Player),
+    )
+}
@@ -0,0 +1,131 @@
+use crate::game::economy::Wallet;
+use crate::game::interact::Interactable;
+use crate::game::interact::OnInteract;
+use crate::game::item::ItemAssets;
+use crate::game::item::pool::ItemKind;
+use crate::game::item::pool::OnGrantItem;
+use crate::game::room::Room;
+use crate::game::room::RoomKind;
+use crate::game::world::Level;
+use crate::game::world::RunSeed;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(ShopPedestal, ShopPurchases)>();
+}
+
+const SHOP_SLOTS: usize = 3;
+const PEDESTAL_SPACING: f32 = 96.0;
+
+/// An item for sale in a shop room.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ShopPedestal {
+    pub item: ItemKind,
+    pub price: u32,
+    /// The [`Room::seed`] of the shop and the index of this pedestal within it.
+    pub slot: (u64, usize),
+}
+
+impl Configure for ShopPedestal {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, stock_shop.in_set(UpdateSystems::SyncEarly));
+        app.add_observer(buy_from_pedestal);
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn stock_shop(
+    mut commands: Commands,
+    run_seed: Res<RunSeed>,
+    purchases: Res<ShopPurchases>,
+    item_assets: Res<ItemAssets>,
+    room_query: Query<(Entity, &Room), Added<Room>>,
+) {
+    for (entity, room) in &room_query {
+        cq!(room.kind == RoomKind::Shop);
+
+        let seed = room.seed(run_seed.0);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let items = ItemKind::roll(&mut rng, SHOP_SLOTS);
+        let center = room.local_center();
+        let first_x = -(items.len().saturating_sub(1) as f32) * PEDESTAL_SPACING / 2.0;
+        for (i, item) in items.into_iter().enumerate() {
+            // Don't restock items that were already bought if the room is respawned.
+            cq!(!purchases.0.contains(&(seed, i)));
+            let pos = center + vec2(first_x + i as f32 * PEDESTAL_SPACING, 0.0);
+            commands.spawn((
+                shop_pedestal(item, (seed, i), &item_assets),
+                Transform::from_translation(pos.extend(4.0)),
+                ChildOf(entity),
+            ));
+        }
+    }
+}
+
+fn shop_pedestal(item: ItemKind, slot: (u64, usize), assets: &ItemAssets) -> impl Bundle {
+    let price = item.price();
+    (
+        Name::new(format!("ShopPedestal({})", item.name())),
+        ShopPedestal { item, price, slot },
+        AseAnimation {
+            aseprite: item.sprite(assets),
+            animation: Animation::default(),
+        },
+        Sprite::default(),
+        Interactable::new(format!("Buy ({price})")),
+        Pickable::default(),
+        Tooltip::cursor(RichText::from_sections(parse_rich(format!(
+            "[b]{}\n[r]{}\nPrice: {price}",
+            item.name(),
+            item.description(),
+        )))),
+        children![(
+            Name::new("PriceLabel"),
+            Text2d::new(price.to_string()),
+            TextFont {
+                font: FONT_HANDLE,
+                font_size: 8.0,
+                ..default()
+            },
+            Transform::from_xyz(0.0, -14.0, 0.0),
+        )],
+    )
+}
+
+fn buy_from_pedestal(
+    trigger: Trigger<OnInteract>,
+    mut commands: Commands,
+    mut purchases: ResMut<ShopPurchases>,
+    pedestal_query: Query<&ShopPedestal>,
+    mut wallet_query: Query<&mut Wallet>,
+) {
+    let pedestal_entity = r!(trigger.get_target());
+    let pedestal = rq!(pedestal_query.get(pedestal_entity));
+    let mut wallet = rq!(wallet_query.get_mut(trigger.actor));
+    rq!(wallet.spend(pedestal.price));
+    purchases.0.insert(pedestal.slot);
+
+    commands
+        .entity(trigger.actor)
+        .trigger(OnGrantItem(pedestal.item));
+    commands.entity(pedestal_entity).despawn();
+}
+
+/// The shop pedestals that have been bought from in the current level, by [`ShopPedestal::slot`].
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct ShopPurchases(pub HashSet<(u64, usize)>);
+
+impl Configure for ShopPurchases {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Level::ANY.on_exit(reset_shop_purchases));
+    }
+}
+
+fn reset_shop_purchases(mut purchases: ResMut<ShopPurchases>) {
+    purchases.0.clear();
+}
@@ -0,0 +1,302 @@
+pub mod barrel;
+pub mod spike_trap;
+pub mod turret;
+
+use crate::game::GameLayer;
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::actor::combat::damage::OnDamage;
+use crate::game::actor::combat::health::Health;
+use crate::game::item::effects::damage_over_time::OnDamageOverTime;
+use crate::game::room::OnRoomClear;
+use crate::game::room::OnRoomEnter;
+use crate::game::room::Room;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        HazardAssets,
+        Hazard,
+        HazardEnabled,
+        DamageZone,
+        HazardRoomToggle,
+    )>();
+    app.add_plugins((barrel::plugin, spike_trap::plugin, turret::plugin));
+}
+
+#[derive(AssetCollection, Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct HazardAssets {
+    #[asset(path = "tiles/Dungeon_Tile.png")]
+    pub dungeon_tiles: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 32, tile_size_y = 32, columns = 10, rows = 10))]
+    pub dungeon_tiles_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "image/Enemies/Mouse_Poop.aseprite")]
+    pub mouse_poop: Handle<Aseprite>,
+    #[asset(path = "image/Enemies/Blob_Projectile.aseprite")]
+    pub arrow: Handle<Aseprite>,
+}
+
+impl Configure for HazardAssets {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_collection::<Self>();
+    }
+}
+
+impl HazardAssets {
+    /// A sprite for a tile in `Dungeon_Tile.png`.
+    pub fn tile(&self, index: usize) -> Sprite {
+        Sprite::from_atlas_image(
+            self.dungeon_tiles.clone(),
+            TextureAtlas {
+                layout: self.dungeon_tiles_layout.clone(),
+                index,
+            },
+        )
+    }
+}
+
+/// Deals damage to actors that touch its sensor collider.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+#[require(HazardEnabled)]
+pub struct Hazard {
+    pub damage: f32,
+    pub kind: DamageKind,
+    /// Seconds between hits while an actor stays in contact. Zero hits once per contact.
+    pub interval: f32,
+    /// Damage per tick of an [`OnDamageOverTime`] status applied on hit. Zero applies none.
+    pub status_damage: f32,
+    pub status_duration: f32,
+    /// Seconds until each target in contact can be hit again.
+    #[reflect(ignore)]
+    cooldowns: HashMap<Entity, f32>,
+}
+
+impl Configure for Hazard {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            tick_hazards
+                .in_set(UpdateSystems::Update)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+impl Default for Hazard {
+    fn default() -> Self {
+        Self {
+            damage: 10.0,
+            kind: DamageKind::Physical,
+            interval: 0.0,
+            status_damage: 0.0,
+            status_duration: 0.0,
+            cooldowns: HashMap::default(),
+        }
+    }
+}
+
+impl Hazard {
+    pub fn new(damage: f32, kind: DamageKind) -> Self {
+        Self {
+            damage,
+            kind,
+            ..default()
+        }
+    }
+
+    pub fn with_interval(mut self, interval: f32) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_status(mut self, damage: f32, duration: f32) -> Self {
+        self.status_damage = damage;
+        self.status_duration = duration;
+        self
+    }
+
+    /// Deal damage and apply the status effect to `target`.
+    pub fn hit(&self, commands: &mut Commands, hazard: Entity, target: Entity) {
+        commands
+            .entity(target)
+            .trigger(OnDamage::new(self.damage, Some(hazard)).with_kind(self.kind));
+        if self.status_damage > 0.0 {
+            commands.entity(target).trigger(OnDamageOverTime {
+                damage: self.status_damage,
+                duration: self.status_duration,
+                interval: 0.5,
+                kind: self.kind,
+            });
+        }
+    }
+}
+
+/// The collision layers for a hazard's sensor.
+pub fn hazard_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Hazard, [GameLayer::Player, GameLayer::Enemy])
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn tick_hazards(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut hazard_query: Query<(Entity, &mut Hazard, &HazardEnabled, &CollidingEntities)>,
+    collider_query: Query<&ColliderOf>,
+    health_query: Query<(), With<Health>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut hazard, enabled, colliding) in &mut hazard_query {
+        if !enabled.0 {
+            hazard.cooldowns.clear();
+            continue;
+        }
+
+        let targets = colliding
+            .iter()
+            .map(|&x| collider_query.get(x).map_or(x, |x| x.body))
+            .filter(|&x| health_query.contains(x))
+            .collect::<HashSet<_>>();
+        hazard.cooldowns.retain(|x, _| targets.contains(x));
+
+        for target in targets {
+            let interval = if hazard.interval > 0.0 {
+                hazard.interval
+            } else {
+                f32::INFINITY
+            };
+            let cooldown = hazard.cooldowns.entry(target).or_insert(0.0);
+            *cooldown -= dt;
+            cq!(*cooldown <= 0.0);
+            *cooldown = interval;
+            hazard.hit(&mut commands, entity, target);
+        }
+    }
+}
+
+/// Whether a hazard is switched on. See [`HazardRoomToggle`].
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct HazardEnabled(pub bool);
+
+impl Configure for HazardEnabled {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for HazardEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// An area that hurts actors standing in it, like a poison puddle. Can be placed from Tiled.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct DamageZone {
+    pub radius: f32,
+}
+
+impl Configure for DamageZone {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, init_damage_zone.in_set(UpdateSystems::SyncEarly));
+    }
+}
+
+impl Default for DamageZone {
+    fn default() -> Self {
+        Self { radius: 16.0 }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn init_damage_zone(
+    mut commands: Commands,
+    assets: Res<HazardAssets>,
+    zone_query: Query<(Entity, &DamageZone, Has<Hazard>), Added<DamageZone>>,
+) {
+    for (entity, zone, has_hazard) in &zone_query {
+        let mut entity = commands.entity(entity);
+        entity.insert((
+            AseAnimation {
+                aseprite: assets.mouse_poop.clone(),
+                animation: Animation::default(),
+            },
+            Sprite::default(),
+            Collider::circle(zone.radius),
+            Sensor,
+            CollidingEntities::default(),
+            hazard_layers(),
+        ));
+        if !has_hazard {
+            entity.insert(
+                Hazard::new(2.0, DamageKind::Poison)
+                    .with_interval(0.5)
+                    .with_status(1.0, 2.0),
+            );
+        }
+    }
+}
+
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub enum HazardToggle {
+    #[default]
+    Keep,
+    Enable,
+    Disable,
+}
+
+impl HazardToggle {
+    fn apply(self, enabled: &mut HazardEnabled) {
+        match self {
+            Self::Keep => {},
+            Self::Enable => enabled.0 = true,
+            Self::Disable => enabled.0 = false,
+        }
+    }
+}
+
+/// Switches a hazard on or off when its room is entered or cleared.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+#[require(HazardEnabled)]
+pub struct HazardRoomToggle {
+    pub on_enter: HazardToggle,
+    pub on_clear: HazardToggle,
+}
+
+impl Configure for HazardRoomToggle {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(toggle_hazards_on_room_enter);
+        app.add_observer(toggle_hazards_on_room_clear);
+    }
+}
+
+fn toggle_hazards_on_room_enter(
+    trigger: Trigger<OnRoomEnter>,
+    room_query: Query<&Room>,
+    mut hazard_query: Query<(&HazardRoomToggle, &mut HazardEnabled, &GlobalTransform)>,
+) {
+    let room = r!(room_query.get(r!(trigger.get_target())));
+    for (toggle, mut enabled, gt) in &mut hazard_query {
+        cq!(room.rect.contains(gt.translation().xy()));
+        toggle.on_enter.apply(&mut enabled);
+    }
+}
+
+fn toggle_hazards_on_room_clear(
+    trigger: Trigger<OnRoomClear>,
+    room_query: Query<&Room>,
+    mut hazard_query: Query<(&HazardRoomToggle, &mut HazardEnabled, &GlobalTransform)>,
+) {
+    let room = r!(room_query.get(r!(trigger.get_target())));
+    for (toggle, mut enabled, gt) in &mut hazard_query {
+        cq!(room.rect.contains(gt.translation().xy()));
+        toggle.on_clear.apply(&mut enabled);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::core::camera::OnCameraShake;
+use crate::game::GameLayer;
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::actor::combat::damage::Knockback;
+use crate::game::actor::combat::health::Health;
+use crate::game::actor::combat::health::OnDeath;
+use crate::game::hazard::Hazard;
+use crate::game::hazard::HazardAssets;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(ExplosiveBarrel, Explosion)>();
+}
+
+const BARREL_TILE: usize = 20;
+const EXPLOSION_SECS: f32 = 0.2;
+
+/// Explodes when destroyed, damaging everything nearby. Can be placed from Tiled.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct ExplosiveBarrel {
+    pub health: f32,
+    pub radius: f32,
+    pub damage: f32,
+}
+
+impl Configure for ExplosiveBarrel {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, init_barrel.in_set(UpdateSystems::SyncEarly));
+        app.add_observer(explode_on_death);
+    }
+}
+
+impl Default for ExplosiveBarrel {
+    fn default() -> Self {
+        Self {
+            health: 20.0,
+            radius: 64.0,
+            damage: 40.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn init_barrel(
+    mut commands: Commands,
+    assets: Res<HazardAssets>,
+    barrel_query: Query<(Entity, &ExplosiveBarrel), Added<ExplosiveBarrel>>,
+) {
+    for (entity, barrel) in &barrel_query {
+        commands.entity(entity).insert((
+            assets.tile(BARREL_TILE),
+            Health::new(barrel.health),
+            RigidBody::Static,
+            Collider::rectangle(24.0, 24.0),
+        ));
+    }
+}
+
+fn explode_on_death(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    barrel_query: Query<(&ExplosiveBarrel, &GlobalTransform)>,
+) {
+    let target = r!(trigger.get_target());
+    let (barrel, gt) = rq!(barrel_query.get(target));
+
+    commands.trigger(OnCameraShake(0.7));
+    commands.trigger(PlaySfx::new(SfxId::Explosion).at(gt.translation().xy()));
+    commands.spawn((
+        Name::new("Explosion"),
+        Explosion {
+            remaining: EXPLOSION_SECS,
+        },
+        Hazard::new(barrel.damage, DamageKind::Explosive).with_status(2.0, 1.5),
+        Knockback(300.0),
+        Sprite::from_color(
+            Color::Srgba(ORANGE_400.with_alpha(0.6)),
+            Vec2::splat(2.0 * barrel.radius),
+        ),
+        Transform::from_translation(gt.translation().xy().extend(7.0)),
+        // Barrels are static, so the explosion needs a body to detect them.
+        RigidBody::Kinematic,
+        Collider::circle(barrel.radius),
+        Sensor,
+        CollisionEventsEnabled,
+        CollidingEntities::default(),
+        // Include the default layer to set off other barrels.
+        CollisionLayers::new(
+            GameLayer::Hazard,
+            [GameLayer::Default, GameLayer::Player, GameLayer::Enemy],
+        ),
+        DespawnOnExitState::<Level>::default(),
+    ));
+}
+
+/// A short-lived [`Hazard`] left behind by an [`ExplosiveBarrel`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Explosion {
+    pub remaining: f32,
+}
+
+impl Configure for Explosion {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            tick_explosion
+                .in_set(UpdateSystems::TickTimers)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn tick_explosion(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut explosion_query: Query<(Entity, &mut Explosion, &mut Sprite)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut explosion, mut sprite) in &mut explosion_query {
+        explosion.remaining -= dt;
+        if explosion.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite
+            .color
+            .set_alpha(0.6 * explosion.remaining / EXPLOSION_SECS);
+    }
+}
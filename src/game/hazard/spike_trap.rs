@@ -0,0 +1,125 @@
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::hazard::Hazard;
+use crate::game::hazard::HazardAssets;
+use crate::game::hazard::HazardEnabled;
+use crate::game::hazard::hazard_layers;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<SpikeTrap>();
+}
+
+const SPIKES_DOWN_TILE: usize = 95;
+const SPIKES_UP_TILE: usize = 96;
+
+/// Spikes that periodically extend from the floor. Can be placed from Tiled.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+#[require(HazardEnabled)]
+pub struct SpikeTrap {
+    /// Seconds the spikes stay extended.
+    pub up_secs: f32,
+    /// Seconds the spikes stay retracted.
+    pub down_secs: f32,
+    /// Seconds into the current cycle.
+    pub elapsed: f32,
+}
+
+impl Configure for SpikeTrap {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                init_spike_trap.in_set(UpdateSystems::SyncEarly),
+                tick_spike_trap
+                    .in_set(UpdateSystems::TickTimers)
+                    .run_if(Pause::is_disabled),
+                (sync_spike_trap_sprite, sync_spike_trap_collider).in_set(UpdateSystems::SyncLate),
+            ),
+        );
+    }
+}
+
+impl Default for SpikeTrap {
+    fn default() -> Self {
+        Self {
+            up_secs: 1.0,
+            down_secs: 1.5,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl SpikeTrap {
+    /// Whether the spikes are extended.
+    pub fn is_armed(&self) -> bool {
+        self.elapsed >= self.down_secs
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn init_spike_trap(
+    mut commands: Commands,
+    assets: Res<HazardAssets>,
+    trap_query: Query<(Entity, Has<Hazard>), Added<SpikeTrap>>,
+) {
+    for (entity, has_hazard) in &trap_query {
+        let mut entity = commands.entity(entity);
+        entity.insert((
+            assets.tile(SPIKES_DOWN_TILE),
+            Collider::rectangle(28.0, 28.0),
+            Sensor,
+            CollidingEntities::default(),
+            hazard_layers(),
+        ));
+        if !has_hazard {
+            entity.insert(Hazard::new(15.0, DamageKind::Physical).with_interval(0.75));
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn tick_spike_trap(time: Res<Time>, mut trap_query: Query<(&mut SpikeTrap, &HazardEnabled)>) {
+    let dt = time.delta_secs();
+    for (mut trap, enabled) in &mut trap_query {
+        if !enabled.0 {
+            trap.elapsed = 0.0;
+            continue;
+        }
+
+        let period = trap.up_secs + trap.down_secs;
+        cq!(period > 0.0);
+        trap.elapsed = (trap.elapsed + dt) % period;
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn sync_spike_trap_sprite(mut trap_query: Query<(&SpikeTrap, &mut Sprite)>) {
+    for (trap, mut sprite) in &mut trap_query {
+        let atlas = c!(sprite.texture_atlas.as_mut());
+        let index = if trap.is_armed() {
+            SPIKES_UP_TILE
+        } else {
+            SPIKES_DOWN_TILE
+        };
+        if atlas.index != index {
+            atlas.index = index;
+        }
+    }
+}
+
+/// Only deal damage while the spikes are extended.
+#[cfg_attr(feature = "native_dev", hot)]
+fn sync_spike_trap_collider(
+    mut commands: Commands,
+    trap_query: Query<(Entity, &SpikeTrap, Has<ColliderDisabled>)>,
+) {
+    for (entity, trap, disabled) in &trap_query {
+        if trap.is_armed() && disabled {
+            commands.entity(entity).remove::<ColliderDisabled>();
+        } else if !trap.is_armed() && !disabled {
+            commands.entity(entity).insert(ColliderDisabled);
+        }
+    }
+}
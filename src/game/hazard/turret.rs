@@ -0,0 +1,138 @@
+use crate::game::GameLayer;
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::actor::combat::health::Health;
+use crate::game::hazard::Hazard;
+use crate::game::hazard::HazardAssets;
+use crate::game::hazard::HazardEnabled;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(ArrowTurret, Arrow)>();
+}
+
+/// Periodically fires arrows in a fixed direction. Can be placed from Tiled.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+#[require(HazardEnabled)]
+pub struct ArrowTurret {
+    pub direction: Vec2,
+    /// Seconds between shots.
+    pub interval: f32,
+    pub speed: f32,
+    pub damage: f32,
+    /// Seconds until the next shot.
+    pub cooldown: f32,
+}
+
+impl Configure for ArrowTurret {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                init_arrow_turret.in_set(UpdateSystems::SyncEarly),
+                fire_arrow_turret
+                    .in_set(UpdateSystems::Update)
+                    .run_if(Pause::is_disabled),
+            ),
+        );
+    }
+}
+
+impl Default for ArrowTurret {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::X,
+            interval: 2.0,
+            speed: 250.0,
+            damage: 10.0,
+            cooldown: 0.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn init_arrow_turret(mut commands: Commands, turret_query: Query<Entity, Added<ArrowTurret>>) {
+    for entity in &turret_query {
+        commands.entity(entity).insert(Sprite::from_color(
+            Color::srgb(0.25, 0.22, 0.3),
+            Vec2::splat(12.0),
+        ));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn fire_arrow_turret(
+    time: Res<Time>,
+    mut commands: Commands,
+    assets: Res<HazardAssets>,
+    mut turret_query: Query<(Entity, &mut ArrowTurret, &HazardEnabled, &GlobalTransform)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut turret, enabled, gt) in &mut turret_query {
+        cq!(enabled.0);
+        turret.cooldown -= dt;
+        cq!(turret.cooldown <= 0.0);
+        turret.cooldown += turret.interval.max(0.1);
+
+        let direction = cq!(Dir2::new(turret.direction));
+        commands.entity(entity).trigger(PlaySfx::new(SfxId::Shoot));
+        commands.spawn((
+            Name::new("Arrow"),
+            Arrow { turret: entity },
+            Hazard::new(turret.damage, DamageKind::Physical),
+            AseAnimation {
+                aseprite: assets.arrow.clone(),
+                animation: Animation::default(),
+            },
+            Sprite::default(),
+            Transform::from_translation(gt.translation().xy().extend(6.0))
+                .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+            RigidBody::Kinematic,
+            LinearVelocity(turret.speed * direction),
+            Collider::circle(3.0),
+            Sensor,
+            CollisionEventsEnabled,
+            CollisionLayers::new(
+                GameLayer::Hazard,
+                [GameLayer::Player, GameLayer::Enemy, GameLayer::Wall],
+            ),
+            DespawnOnExitState::<Level>::default(),
+        ));
+    }
+}
+
+/// A projectile fired by an [`ArrowTurret`]. Hits once, then despawns.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Arrow {
+    pub turret: Entity,
+}
+
+impl Configure for Arrow {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(hit_with_arrow);
+    }
+}
+
+fn hit_with_arrow(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    arrow_query: Query<(&Arrow, &Hazard)>,
+    health_query: Query<(), With<Health>>,
+) {
+    let arrow = r!(trigger.get_target());
+    let (arrow_data, hazard) = rq!(arrow_query.get(arrow));
+    let target = trigger.body.unwrap_or(trigger.collider);
+    // Don't hit the turret that fired the arrow.
+    rq!(target != arrow_data.turret);
+
+    // Walls and other props just stop the arrow.
+    if health_query.contains(target) {
+        hazard.hit(&mut commands, arrow, target);
+    }
+    commands.entity(arrow).despawn();
+}
@@ -0,0 +1,196 @@
+use crate::game::actor::camera_cutie::CameraSequence;
+use crate::game::actor::combat::health::Downed;
+use crate::game::actor::movement::input::PlayerAction;
+use crate::game::world::Level;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Interactable, Interactor, InteractPrompt, OnInteract)>();
+}
+
+/// A world object that can be interacted with, such as a chest, door or NPC.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct Interactable {
+    /// Text shown above the object while it's the nearest interactable.
+    pub prompt: String,
+    /// The max distance from which the object can be interacted with.
+    pub radius: f32,
+}
+
+impl Configure for Interactable {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl Default for Interactable {
+    fn default() -> Self {
+        Self {
+            prompt: "Interact".to_string(),
+            radius: 40.0,
+        }
+    }
+}
+
+impl Interactable {
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            ..default()
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+}
+
+/// An actor that can interact with [`Interactable`]s using [`PlayerAction::Interact`].
+#[derive(Component, Reflect, Default, PartialEq, Debug)]
+#[reflect(Component)]
+pub struct Interactor {
+    /// The nearest interactable within range.
+    pub nearest: Option<Entity>,
+}
+
+impl Configure for Interactor {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                update_nearest_interactable
+                    .in_set(UpdateSystems::SyncEarly)
+                    .run_if(Pause::is_disabled),
+                interact
+                    .in_set(UpdateSystems::RecordInput)
+                    .run_if(Pause::is_disabled.and(CameraSequence::is_inactive)),
+            ),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_nearest_interactable(
+    mut interactor_query: Query<(&mut Interactor, &GlobalTransform)>,
+    interactable_query: Query<(Entity, &Interactable, &GlobalTransform)>,
+) {
+    for (mut interactor, interactor_gt) in &mut interactor_query {
+        let pos = interactor_gt.translation().xy();
+        let nearest = interactable_query
+            .iter()
+            .map(|(entity, interactable, gt)| {
+                let distance = gt.translation().xy().distance(pos);
+                (entity, interactable, distance)
+            })
+            .filter(|&(_, interactable, distance)| distance <= interactable.radius)
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(entity, ..)| entity);
+        interactor.set_if_neq(Interactor { nearest });
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn interact(
+    mut commands: Commands,
+    interactor_query: Query<(Entity, &Interactor, &ActionState<PlayerAction>), Without<Downed>>,
+) {
+    for (entity, interactor, action) in &interactor_query {
+        cq!(action.just_pressed(&PlayerAction::Interact));
+        let target = cq!(interactor.nearest);
+        commands
+            .entity(target)
+            .trigger(OnInteract { actor: entity });
+    }
+}
+
+/// Triggered on an [`Interactable`] when an actor interacts with it.
+#[derive(Event, Reflect, Debug)]
+pub struct OnInteract {
+    pub actor: Entity,
+}
+
+impl Configure for OnInteract {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+const PROMPT_OFFSET: Vec2 = Vec2::new(0.0, 24.0);
+
+/// The prompt shown above an [`Interactor`]'s nearest [`Interactable`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct InteractPrompt {
+    pub interactor: Entity,
+}
+
+impl Configure for InteractPrompt {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (spawn_interact_prompt, sync_interact_prompt)
+                .chain()
+                .in_set(UpdateSystems::SyncLate),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_interact_prompt(
+    mut commands: Commands,
+    interactor_query: Query<Entity, Added<Interactor>>,
+) {
+    for entity in &interactor_query {
+        commands.spawn((
+            Name::new("InteractPrompt"),
+            InteractPrompt { interactor: entity },
+            Text2d::default(),
+            TextFont {
+                font: BOLD_FONT_HANDLE,
+                font_size: 8.0,
+                ..default()
+            },
+            Visibility::Hidden,
+            DespawnOnExitState::<Level>::Recursive,
+        ));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn sync_interact_prompt(
+    mut commands: Commands,
+    mut prompt_query: Query<(
+        Entity,
+        &InteractPrompt,
+        &mut Text2d,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+    interactor_query: Query<&Interactor>,
+    interactable_query: Query<(&Interactable, &GlobalTransform)>,
+) {
+    for (entity, prompt, mut text, mut transform, mut visibility) in &mut prompt_query {
+        let Ok(interactor) = interactor_query.get(prompt.interactor) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let Some((interactable, gt)) = interactor
+            .nearest
+            .and_then(|nearest| interactable_query.get(nearest).ok())
+        else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        visibility.set_if_neq(Visibility::Inherited);
+        if text.0 != interactable.prompt {
+            text.0.clone_from(&interactable.prompt);
+        }
+        let pos = gt.translation().xy() + PROMPT_OFFSET;
+        transform.translation = pos.extend(10.0);
+    }
+}
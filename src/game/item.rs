@@ -1,7 +1,48 @@
 use crate::prelude::*;
 
+pub mod chest;
 pub mod effects;
+pub mod loot;
+pub mod pickup;
+pub mod pool;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(effects::plugin);
+    app.configure::<ItemAssets>();
+    app.add_plugins((
+        chest::plugin,
+        effects::plugin,
+        loot::plugin,
+        pickup::plugin,
+        pool::plugin,
+    ));
+}
+
+#[derive(AssetCollection, Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct ItemAssets {
+    #[asset(path = "image/Items/Health_Item.aseprite")]
+    pub health_item: Handle<Aseprite>,
+    #[asset(path = "image/Items/Lesser_Cheese.aseprite")]
+    pub lesser_cheese: Handle<Aseprite>,
+    #[asset(path = "image/Items/Fire_Pepper_Jack.aseprite")]
+    pub fire_pepper_jack: Handle<Aseprite>,
+    #[asset(path = "image/Items/Repurposed_Rat_Poison.aseprite")]
+    pub repurposed_rat_poison: Handle<Aseprite>,
+    #[asset(path = "image/Items/Totem_Of_Vampirism.aseprite")]
+    pub totem_of_vampirism: Handle<Aseprite>,
+    #[asset(path = "image/Items/Extra_Protein_Cheese.aseprite")]
+    pub extra_protein_cheese: Handle<Aseprite>,
+    #[asset(path = "image/Items/Mysterious_Goop.aseprite")]
+    pub mysterious_goop: Handle<Aseprite>,
+    #[asset(path = "image/Items/Bat_Item.aseprite")]
+    pub bat_item: Handle<Aseprite>,
+    #[asset(path = "image/Items/Chest.aseprite")]
+    pub chest: Handle<Aseprite>,
+}
+
+impl Configure for ItemAssets {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_collection::<Self>();
+    }
 }
@@ -0,0 +1,170 @@
+use crate::game::interact::Interactable;
+use crate::game::interact::OnInteract;
+use crate::game::item::ItemAssets;
+use crate::game::item::loot::LootConfig;
+use crate::game::item::loot::spawn_loot;
+use crate::game::room::OnRoomClear;
+use crate::game::room::Room;
+use crate::game::world::Level;
+use crate::game::world::RunSeed;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Chest, ChestId, OpenedChests, OnOpenChest)>();
+}
+
+/// A treasure chest that opens on interaction or when its room is cleared.
+///
+/// Place a Tiled object with this class to spawn a chest.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct Chest;
+
+impl Configure for Chest {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, init_chest.in_set(UpdateSystems::SyncEarly));
+        app.add_observer(open_chest_on_interact);
+        app.add_observer(open_chests_on_room_clear);
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn init_chest(
+    mut commands: Commands,
+    item_assets: Res<ItemAssets>,
+    opened_chests: Res<OpenedChests>,
+    chest_query: Query<(Entity, &Transform), (With<Chest>, Without<ChestId>)>,
+    parent_query: Query<&ChildOf>,
+    room_query: Query<&Room>,
+) {
+    for (entity, transform) in &chest_query {
+        // Wait for the containing room to be initialized.
+        let room = cq!(parent_query
+            .iter_ancestors(entity)
+            .find_map(|x| room_query.get(x).ok()));
+        let id = ChestId {
+            room: room.key,
+            local: transform.translation.xy().round().as_ivec2(),
+        };
+        let opened = opened_chests.0.contains(&id);
+
+        let mut chest = commands.entity(entity);
+        chest.insert((
+            id,
+            AseAnimation {
+                aseprite: item_assets.chest.clone(),
+                animation: Animation::tag(if opened { "Opened" } else { "Closed" }),
+            },
+            Sprite::default(),
+        ));
+        if !opened {
+            chest.insert(Interactable::new("Open"));
+        }
+    }
+}
+
+fn open_chest_on_interact(
+    trigger: Trigger<OnInteract>,
+    mut commands: Commands,
+    chest_query: Query<(), With<Chest>>,
+) {
+    let target = r!(trigger.get_target());
+    rq!(chest_query.contains(target));
+    commands.entity(target).trigger(OnOpenChest);
+}
+
+fn open_chests_on_room_clear(
+    trigger: Trigger<OnRoomClear>,
+    mut commands: Commands,
+    room_query: Query<&Room>,
+    chest_query: Query<(Entity, &ChestId)>,
+) {
+    let room = r!(room_query.get(r!(trigger.get_target())));
+    for (entity, id) in &chest_query {
+        cq!(id.room == room.key);
+        commands.entity(entity).trigger(OnOpenChest);
+    }
+}
+
+/// Identifies a [`Chest`] by its position, so it stays opened if its room is respawned.
+#[derive(Component, Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[reflect(Component)]
+pub struct ChestId {
+    /// The [`Room::key`] of the containing room.
+    pub room: IVec2,
+    /// The position of the chest within its map layer.
+    pub local: IVec2,
+}
+
+impl Configure for ChestId {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+impl ChestId {
+    /// A seed for rolling the chest's rewards during a run.
+    fn seed(self, run_seed: u64) -> u64 {
+        let pack = |v: IVec2| ((v.x as u32 as u64) << 32) | v.y as u32 as u64;
+        run_seed
+            ^ pack(self.room).rotate_left(17)
+            ^ pack(self.local).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    }
+}
+
+/// The chests that have been opened in the current level.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct OpenedChests(pub HashSet<ChestId>);
+
+impl Configure for OpenedChests {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Level::ANY.on_exit(reset_opened_chests));
+    }
+}
+
+fn reset_opened_chests(mut opened_chests: ResMut<OpenedChests>) {
+    opened_chests.0.clear();
+}
+
+/// Triggered on a [`Chest`] to open it and spawn its rewards.
+#[derive(Event, Reflect, Debug)]
+pub struct OnOpenChest;
+
+impl Configure for OnOpenChest {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(open_chest);
+    }
+}
+
+fn open_chest(
+    trigger: Trigger<OnOpenChest>,
+    mut commands: Commands,
+    mut opened_chests: ResMut<OpenedChests>,
+    run_seed: Res<RunSeed>,
+    loot_config: ConfigRef<LootConfig>,
+    item_assets: Res<ItemAssets>,
+    mut chest_query: Query<(&ChestId, &GlobalTransform, &mut AseAnimation)>,
+) {
+    let target = r!(trigger.get_target());
+    let (&id, gt, mut anim) = rq!(chest_query.get_mut(target));
+    rq!(opened_chests.0.insert(id));
+    let loot_config = r!(loot_config.get());
+
+    anim.animation = Animation::tag("Open").with_repeat(AnimationRepeat::Count(1));
+    commands.entity(target).remove::<Interactable>();
+
+    let mut rng = StdRng::seed_from_u64(id.seed(run_seed.0));
+    let loot = loot_config.chest.roll(&mut rng);
+    spawn_loot(
+        &mut commands,
+        &mut rng,
+        &item_assets,
+        &loot,
+        gt.translation().xy(),
+    );
+}
@@ -1,3 +1,4 @@
+use crate::game::actor::combat::damage::DamageKind;
 use crate::game::actor::combat::damage::OnDamage;
 use crate::prelude::*;
 
@@ -36,6 +37,7 @@ pub struct OnDamageOverTime {
     pub damage: f32,
     pub duration: f32,
     pub interval: f32,
+    pub kind: DamageKind,
 }
 
 impl Configure for OnDamageOverTime {
@@ -61,6 +63,7 @@ pub fn apply_dot_on_damage(
         damage: dot.damage,
         duration: dot.duration,
         interval: dot.interval,
+        kind: DamageKind::Physical,
     });
 }
 
@@ -71,6 +74,7 @@ pub fn spawn_dot_effect(trigger: Trigger<OnDamageOverTime>, mut commands: Comman
         damage: trigger.damage,
         remaining: trigger.duration,
         interval: trigger.interval,
+        kind: trigger.kind,
         timer: Timer::from_seconds(trigger.interval, TimerMode::Repeating),
     });
 }
@@ -81,6 +85,7 @@ pub struct ActiveDOT {
     pub damage: f32,
     pub remaining: f32,
     pub interval: f32,
+    pub kind: DamageKind,
     pub timer: Timer,
 }
 
@@ -110,9 +115,11 @@ pub fn dot_tick_system(
             continue;
         }
 
-        commands
-            .entity(entity)
-            .trigger(OnDamage::new(effect.damage, None));
+        commands.entity(entity).trigger(
+            OnDamage::new(effect.damage, None)
+                .with_kind(effect.kind)
+                .as_tick(),
+        );
 
         if effect.remaining < 0.0 {
             commands.entity(entity).remove::<ActiveDOT>();
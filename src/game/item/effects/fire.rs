@@ -1,3 +1,4 @@
+use crate::game::actor::combat::damage::DamageKind;
 use crate::game::actor::combat::health::Health;
 use crate::game::item::effects::damage_over_time::OnDamageOverTime;
 use crate::prelude::*;
@@ -8,7 +9,7 @@ pub(in crate::game) fn plugin(app: &mut App) {
 
 const FIRE_DAMAGE: f32 = 5.0;
 
-#[derive(Component, Reflect, Debug)]
+#[derive(Component, Reflect, Copy, Clone, Debug)]
 #[reflect(Component)]
 pub(crate) struct AppliesFire {
     pub duration: f32,
@@ -72,5 +73,6 @@ fn start_fire_on_collision(
         damage: FIRE_DAMAGE,
         duration: fire.duration,
         interval: 0.5,
+        kind: DamageKind::Fire,
     });
 }
@@ -1,4 +1,4 @@
-use crate::game::actor::combat::damage::OnDamage;
+use crate::game::actor::combat::damage::OnDamaged;
 use crate::game::actor::combat::heal::OnHeal;
 use crate::prelude::*;
 
@@ -30,12 +30,13 @@ impl Configure for OnLifeSteal {
 }
 
 pub fn apply_lifesteal_on_damage(
-    trigger: Trigger<OnDamage>,
+    trigger: Trigger<OnDamaged>,
     mut commands: Commands,
     parent_query: Query<&ChildOf>,
     life_steal_query: Query<&LifeSteal>,
 ) {
     let target = rq!(trigger.attacker);
+    // Only steal the damage that got through shield and armor.
     let damage = trigger.damage;
 
     let parent = rq!(parent_query.get(target)).parent();
@@ -46,5 +47,5 @@ pub fn apply_lifesteal_on_damage(
 
     commands
         .entity(parent)
-        .trigger(OnHeal(damage * life_steal.steal_percent));
+        .trigger(OnHeal::flat(damage * life_steal.steal_percent));
 }
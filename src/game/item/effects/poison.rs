@@ -1,3 +1,4 @@
+use crate::game::actor::combat::damage::DamageKind;
 use crate::game::actor::combat::health::Health;
 use crate::game::item::effects::damage_over_time::OnDamageOverTime;
 use crate::prelude::*;
@@ -8,11 +9,11 @@ pub(super) fn plugin(app: &mut App) {
 
 const POISON_DAMAGE: f32 = 2.0;
 
-#[derive(Component, Reflect, Debug)]
+#[derive(Component, Reflect, Copy, Clone, Debug)]
 #[reflect(Component)]
 pub struct AppliesPoison {
-    proc_chance: f64,
-    duration: f32,
+    pub proc_chance: f64,
+    pub duration: f32,
 }
 
 impl Configure for AppliesPoison {
@@ -63,5 +64,6 @@ fn start_poison_on_collision(
         damage: POISON_DAMAGE,
         duration: poison.duration,
         interval: 0.5,
+        kind: DamageKind::Poison,
     });
 }
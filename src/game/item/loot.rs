@@ -0,0 +1,112 @@
+use crate::game::economy::currency_pickup;
+use crate::game::item::ItemAssets;
+use crate::game::item::pickup::HealPickup;
+use crate::game::item::pickup::ItemPickup;
+use crate::game::item::pickup::pickup;
+use crate::game::item::pool::ItemKind;
+use crate::game::world::Level;
+use crate::prelude::*;
+use std::f32::consts::TAU;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<ConfigHandle<LootConfig>>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LootConfig {
+    pub chest: LootTable,
+}
+
+impl Config for LootConfig {
+    const FILE: &'static str = "loot.ron";
+}
+
+/// A weighted list of rewards.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LootTable {
+    /// How many entries to roll.
+    pub rolls: u32,
+    pub entries: Vec<LootEntry>,
+}
+
+impl LootTable {
+    pub fn roll(&self, rng: &mut impl Rng) -> Vec<Loot> {
+        (0..self.rolls)
+            .filter_map(|_| self.entries.choose_weighted(rng, |x| x.weight).ok())
+            .map(|x| x.loot)
+            .collect()
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LootEntry {
+    pub weight: f32,
+    pub loot: Loot,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum Loot {
+    /// A random amount of currency.
+    Currency { min: u32, max: u32 },
+    /// A flat heal.
+    Heal(f32),
+    /// A heal by a fraction of max health, with any excess converted into shield.
+    Overheal(f32),
+    /// A random item from the pool.
+    Item,
+}
+
+const LOOT_SCATTER: f32 = 24.0;
+
+/// Spawn the rewards for `loot` scattered around `center`.
+pub fn spawn_loot(
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+    item_assets: &ItemAssets,
+    loot: &[Loot],
+    center: Vec2,
+) {
+    for &loot in loot {
+        let offset = Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(8.0..LOOT_SCATTER);
+        let transform = Transform::from_translation((center + offset).extend(4.0));
+        match loot {
+            Loot::Currency { min, max } => {
+                let amount = rng.gen_range(min..=max.max(min));
+                cq!(amount > 0);
+                commands.spawn((
+                    currency_pickup(amount),
+                    transform,
+                    DespawnOnExitState::<Level>::default(),
+                ));
+            },
+            Loot::Heal(amount) => {
+                commands.spawn((
+                    pickup("Health Item", item_assets.health_item.clone()),
+                    HealPickup::flat(amount),
+                    transform,
+                    DespawnOnExitState::<Level>::default(),
+                ));
+            },
+            Loot::Overheal(fraction) => {
+                commands.spawn((
+                    pickup("Lesser Cheese", item_assets.lesser_cheese.clone()),
+                    HealPickup::percent(fraction).with_overheal_to_shield(),
+                    transform,
+                    DespawnOnExitState::<Level>::default(),
+                ));
+            },
+            Loot::Item => {
+                let item = *c!(ItemKind::ALL.choose(rng));
+                commands.spawn((
+                    pickup(item.name(), item.sprite(item_assets)),
+                    ItemPickup(item),
+                    transform,
+                    DespawnOnExitState::<Level>::default(),
+                ));
+            },
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::game::GameLayer;
+use crate::game::actor::combat::heal::HealAmount;
+use crate::game::actor::combat::heal::OnHeal;
+use crate::game::actor::player::Player;
+use crate::game::item::pool::ItemKind;
+use crate::game::item::pool::OnGrantItem;
+use crate::game::sfx::{PlaySfx, SfxId};
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(HealPickup, ItemPickup)>();
+}
+
+/// A consumable that heals the player on contact.
+#[derive(Component, Reflect, Copy, Clone, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct HealPickup {
+    pub amount: HealAmount,
+    pub overheal_to_shield: bool,
+}
+
+impl Configure for HealPickup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(consume_heal_pickup);
+    }
+}
+
+impl HealPickup {
+    pub fn flat(amount: f32) -> Self {
+        Self {
+            amount: HealAmount::Flat(amount),
+            overheal_to_shield: false,
+        }
+    }
+
+    pub fn percent(fraction: f32) -> Self {
+        Self {
+            amount: HealAmount::Percent(fraction),
+            overheal_to_shield: false,
+        }
+    }
+
+    pub fn with_overheal_to_shield(mut self) -> Self {
+        self.overheal_to_shield = true;
+        self
+    }
+}
+
+fn consume_heal_pickup(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    pickup_query: Query<&HealPickup>,
+    player_query: Query<(), With<Player>>,
+) {
+    let pickup_entity = r!(trigger.get_target());
+    let pickup = rq!(pickup_query.get(pickup_entity));
+    let player = rq!(trigger.body);
+    rq!(player_query.contains(player));
+
+    commands
+        .entity(player)
+        .trigger(OnHeal {
+            amount: pickup.amount,
+            overheal_to_shield: pickup.overheal_to_shield,
+        })
+        .trigger(PlaySfx::new(SfxId::Pickup));
+    commands.entity(pickup_entity).despawn();
+}
+
+/// An item from the pool that is granted to the player on contact.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component)]
+pub struct ItemPickup(pub ItemKind);
+
+impl Configure for ItemPickup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(consume_item_pickup);
+    }
+}
+
+fn consume_item_pickup(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    pickup_query: Query<&ItemPickup>,
+    player_query: Query<(), With<Player>>,
+) {
+    let pickup_entity = r!(trigger.get_target());
+    let pickup = rq!(pickup_query.get(pickup_entity));
+    let player = rq!(trigger.body);
+    rq!(player_query.contains(player));
+
+    commands
+        .entity(player)
+        .trigger(OnGrantItem(pickup.0))
+        .trigger(PlaySfx::new(SfxId::Pickup));
+    commands.entity(pickup_entity).despawn();
+}
+
+pub fn pickup(name: &'static str, sprite: Handle<Aseprite>) -> impl Bundle {
+    (
+        Name::new(name),
+        AseAnimation {
+            aseprite: sprite,
+            animation: Animation::default(),
+        },
+        Sprite::default(),
+        Collider::circle(8.0),
+        Sensor,
+        CollisionEventsEnabled,
+        CollisionLayers::new(GameLayer::Pickup, GameLayer::Player),
+    )
+}
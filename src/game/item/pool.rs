@@ -0,0 +1,167 @@
+use crate::game::actor::combat::heal::OnHeal;
+use crate::game::actor::combat::health::Health;
+use crate::game::actor::combat::shield::Armor;
+use crate::game::actor::movement::Movement;
+use crate::game::item::ItemAssets;
+use crate::game::item::effects::fire::AppliesFire;
+use crate::game::item::effects::life_steal::LifeSteal;
+use crate::game::item::effects::poison::AppliesPoison;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Inventory, OnGrantItem)>();
+}
+
+/// An item that can be found or bought during a run.
+#[derive(Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ItemKind {
+    FirePepperJack,
+    RepurposedRatPoison,
+    TotemOfVampirism,
+    ExtraProteinCheese,
+    MysteriousGoop,
+    BatItem,
+}
+
+impl ItemKind {
+    /// The item pool.
+    pub const ALL: [Self; 6] = [
+        Self::FirePepperJack,
+        Self::RepurposedRatPoison,
+        Self::TotemOfVampirism,
+        Self::ExtraProteinCheese,
+        Self::MysteriousGoop,
+        Self::BatItem,
+    ];
+
+    /// Roll up to `count` distinct items from the pool.
+    pub fn roll(rng: &mut impl Rng, count: usize) -> Vec<Self> {
+        Self::ALL.choose_multiple(rng, count).copied().collect()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FirePepperJack => "Fire Pepper Jack",
+            Self::RepurposedRatPoison => "Repurposed Rat Poison",
+            Self::TotemOfVampirism => "Totem of Vampirism",
+            Self::ExtraProteinCheese => "Extra Protein Cheese",
+            Self::MysteriousGoop => "Mysterious Goop",
+            Self::BatItem => "Bat Wings",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::FirePepperJack => "Shots are more likely to burn.",
+            Self::RepurposedRatPoison => "Shots are more likely to poison.",
+            Self::TotemOfVampirism => "Steal life from enemies.",
+            Self::ExtraProteinCheese => "Increases max health.",
+            Self::MysteriousGoop => "Reduces damage taken.",
+            Self::BatItem => "Move faster.",
+        }
+    }
+
+    pub fn price(self) -> u32 {
+        match self {
+            Self::FirePepperJack | Self::RepurposedRatPoison => 15,
+            Self::TotemOfVampirism => 25,
+            Self::ExtraProteinCheese => 20,
+            Self::MysteriousGoop | Self::BatItem => 10,
+        }
+    }
+
+    pub fn sprite(self, assets: &ItemAssets) -> Handle<Aseprite> {
+        match self {
+            Self::FirePepperJack => &assets.fire_pepper_jack,
+            Self::RepurposedRatPoison => &assets.repurposed_rat_poison,
+            Self::TotemOfVampirism => &assets.totem_of_vampirism,
+            Self::ExtraProteinCheese => &assets.extra_protein_cheese,
+            Self::MysteriousGoop => &assets.mysterious_goop,
+            Self::BatItem => &assets.bat_item,
+        }
+        .clone()
+    }
+}
+
+/// The items an actor has collected this run.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Inventory(pub Vec<ItemKind>);
+
+impl Configure for Inventory {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Adds an item to the target's [`Inventory`] and applies its effect.
+#[derive(Event, Reflect, Debug)]
+pub struct OnGrantItem(pub ItemKind);
+
+impl Configure for OnGrantItem {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(grant_item);
+    }
+}
+
+fn grant_item(
+    trigger: Trigger<OnGrantItem>,
+    mut commands: Commands,
+    mut target_query: Query<(
+        &mut Inventory,
+        Option<&mut AppliesFire>,
+        Option<&mut AppliesPoison>,
+        Option<&mut LifeSteal>,
+        Option<&mut Health>,
+        Option<&mut Armor>,
+        Option<&mut Movement>,
+    )>,
+) {
+    let target = r!(trigger.get_target());
+    let (mut inventory, fire, poison, life_steal, health, armor, movement) =
+        r!(target_query.get_mut(target));
+    let item = trigger.0;
+    inventory.0.push(item);
+
+    match item {
+        ItemKind::FirePepperJack => {
+            if let Some(mut fire) = fire {
+                fire.proc_chance = (fire.proc_chance + 0.25).min(1.0);
+            } else {
+                commands.entity(target).insert(AppliesFire::new(0.25));
+            }
+        },
+        ItemKind::RepurposedRatPoison => {
+            if let Some(mut poison) = poison {
+                poison.proc_chance = (poison.proc_chance + 0.25).min(1.0);
+            } else {
+                commands.entity(target).insert(AppliesPoison::new(0.25));
+            }
+        },
+        ItemKind::TotemOfVampirism => {
+            if let Some(mut life_steal) = life_steal {
+                life_steal.steal_percent += 0.1;
+            } else {
+                commands.entity(target).insert(LifeSteal {
+                    proc_percent: 0.5,
+                    steal_percent: 0.2,
+                });
+            }
+        },
+        ItemKind::ExtraProteinCheese => {
+            r!(health).max += 100.0;
+            commands.entity(target).trigger(OnHeal::flat(100.0));
+        },
+        ItemKind::MysteriousGoop => {
+            if let Some(mut armor) = armor {
+                armor.flat += 2.0;
+            } else {
+                commands.entity(target).insert(Armor::flat(2.0));
+            }
+        },
+        ItemKind::BatItem => {
+            r!(movement).speed *= 1.15;
+        },
+    }
+}
@@ -0,0 +1,330 @@
+use crate::core::camera::CameraBounds;
+use crate::core::camera::CameraLookAhead;
+use crate::core::camera::CameraRoot;
+use crate::core::camera::CameraZoom;
+use crate::game::actor::combat::health::Downed;
+use crate::game::actor::combat::health::OnDeath;
+use crate::game::actor::enemy::Enemy;
+use crate::game::actor::player::Player;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Room, InRoom, CurrentRoom, OnRoomEnter, OnRoomClear)>();
+}
+
+#[derive(Reflect, Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub enum RoomKind {
+    #[default]
+    Empty,
+    Medium,
+    Courtyard,
+    Treasure,
+    Boss,
+    Hall,
+    Shop,
+}
+
+impl RoomKind {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Empty" => Self::Empty,
+            "Medium" => Self::Medium,
+            "Courtyard" | "CourtyardEmpty" => Self::Courtyard,
+            "Treasure" => Self::Treasure,
+            "Boss" => Self::Boss,
+            "Hall" => Self::Hall,
+            "Shop" => Self::Shop,
+            _ => return None,
+        })
+    }
+}
+
+/// Camera behavior while the player is inside a [`Room`].
+///
+/// Read from the `camera_zoom`, `camera_fit`, `camera_look_ahead` and `camera_clamp`
+/// map properties.
+#[derive(Reflect, Copy, Clone, Debug)]
+pub struct RoomCamera {
+    /// The projection scale. Larger values zoom out.
+    pub zoom: f32,
+    /// Whether to zoom out far enough to show the whole room, overriding `zoom`.
+    pub fit: bool,
+    /// How far ahead of the player the camera leads, in world units.
+    pub look_ahead: f32,
+    /// Whether to keep the view inside the room.
+    pub clamp: bool,
+}
+
+impl RoomCamera {
+    fn from_properties(properties: &tiled::Properties, kind: RoomKind) -> Self {
+        let float = |key: &str| match properties.get(key)? {
+            tiled::PropertyValue::FloatValue(x) => Some(*x),
+            tiled::PropertyValue::IntValue(x) => Some(*x as f32),
+            _ => None,
+        };
+        let bool = |key: &str| match properties.get(key)? {
+            tiled::PropertyValue::BoolValue(x) => Some(*x),
+            _ => None,
+        };
+
+        Self {
+            zoom: float("camera_zoom").unwrap_or(1.0),
+            // Show the whole arena in boss rooms by default.
+            fit: bool("camera_fit").unwrap_or(kind == RoomKind::Boss),
+            look_ahead: float("camera_look_ahead").unwrap_or(48.0),
+            clamp: bool("camera_clamp").unwrap_or(true),
+        }
+    }
+}
+
+/// A Tiled map in the current level.
+///
+/// The kind is read from the `room` map property if present, or else from the name of
+/// the folder containing the map (e.g. `maps/Dungeon/Treasure/MapTRBL.tmx`).
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Room {
+    pub kind: RoomKind,
+    /// The position of the map within the level, which identifies the room.
+    pub key: IVec2,
+    /// The size of a single tile.
+    pub tile_size: Vec2,
+    /// The size of the whole map.
+    pub size: Vec2,
+    /// The area covered by the map in world space.
+    pub rect: Rect,
+    /// The camera behavior inside the room.
+    pub camera: RoomCamera,
+    /// The number of enemies that belong to the room and are still alive.
+    pub enemies: usize,
+    /// Whether every enemy that belongs to the room has been defeated.
+    ///
+    /// Rooms without enemies, like treasure rooms and shops, are never cleared.
+    pub cleared: bool,
+}
+
+impl Configure for Room {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                insert_room.in_set(UpdateSystems::SyncEarly),
+                assign_room_enemies
+                    .in_set(UpdateSystems::Update)
+                    .run_if(Pause::is_disabled),
+                sync_room_rect.in_set(UpdateSystems::SyncLate),
+            ),
+        );
+    }
+}
+
+impl Room {
+    /// The center of the room relative to the map entity.
+    pub fn local_center(&self) -> Vec2 {
+        // `TilemapAnchor::None` puts the center of tile (0, 0) at the map origin.
+        self.size / 2.0 - self.tile_size / 2.0
+    }
+
+    /// A seed for rolling room-specific content during a run.
+    pub fn seed(&self, run_seed: u64) -> u64 {
+        let key = ((self.key.x as u32 as u64) << 32) | self.key.y as u32 as u64;
+        run_seed ^ key.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn insert_room(
+    mut commands: Commands,
+    map_assets: Res<Assets<TiledMap>>,
+    map_query: Query<(Entity, &TiledMapHandle, &Transform), Without<Room>>,
+) {
+    for (entity, handle, transform) in &map_query {
+        let map = &cq!(map_assets.get(&handle.0)).map;
+        let kind = map
+            .properties
+            .get("room")
+            .and_then(|value| match value {
+                tiled::PropertyValue::StringValue(name) => RoomKind::from_name(name),
+                _ => None,
+            })
+            .or_else(|| {
+                let folder = handle.0.path()?.path().parent()?.file_name()?;
+                RoomKind::from_name(folder.to_str()?)
+            })
+            .unwrap_or_default();
+        let tile_size = vec2(map.tile_width as f32, map.tile_height as f32);
+
+        commands.entity(entity).insert(Room {
+            kind,
+            key: transform.translation.xy().round().as_ivec2(),
+            tile_size,
+            size: tile_size * vec2(map.width as f32, map.height as f32),
+            rect: Rect::default(),
+            camera: RoomCamera::from_properties(&map.properties, kind),
+            enemies: 0,
+            cleared: false,
+        });
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn sync_room_rect(
+    mut room_query: Query<
+        (&mut Room, &GlobalTransform),
+        Or<(Added<Room>, Changed<GlobalTransform>)>,
+    >,
+) {
+    for (mut room, gt) in &mut room_query {
+        let min = gt.translation().xy() - room.tile_size / 2.0;
+        room.rect = Rect::from_corners(min, min + room.size);
+    }
+}
+
+/// The [`Room`] an enemy belongs to.
+///
+/// Assigned once the room containing the enemy has loaded, so enemies that wander into
+/// another room still count towards clearing their own room.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct InRoom(pub Entity);
+
+impl Configure for InRoom {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(clear_room_on_enemy_death);
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn assign_room_enemies(
+    mut commands: Commands,
+    mut room_query: Query<(Entity, &mut Room)>,
+    enemy_query: Query<(Entity, &GlobalTransform), (With<Enemy>, Without<InRoom>)>,
+) {
+    for (enemy, gt) in &enemy_query {
+        let pos = gt.translation().xy();
+        let (room_entity, mut room) = cq!(room_query
+            .iter_mut()
+            .find(|(_, room)| room.rect.contains(pos)));
+        room.enemies += 1;
+        commands.entity(enemy).insert(InRoom(room_entity));
+    }
+}
+
+fn clear_room_on_enemy_death(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    enemy_query: Query<&InRoom>,
+    mut room_query: Query<&mut Room>,
+) {
+    let target = r!(trigger.get_target());
+    let in_room = rq!(enemy_query.get(target));
+    let mut room = rq!(room_query.get_mut(in_room.0));
+    room.enemies = room.enemies.saturating_sub(1);
+    rq!(room.enemies == 0 && !room.cleared);
+
+    room.cleared = true;
+    commands.entity(in_room.0).trigger(OnRoomClear);
+}
+
+/// The room the player is currently in.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct CurrentRoom(pub Option<Entity>);
+
+impl Configure for CurrentRoom {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            (
+                update_current_room,
+                apply_room_camera.run_if(resource_changed::<CurrentRoom>),
+            )
+                .chain()
+                .in_set(UpdateSystems::SyncEarly)
+                .after(insert_room)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_current_room(
+    mut commands: Commands,
+    mut current_room: ResMut<CurrentRoom>,
+    player_query: Query<(&Player, &GlobalTransform), Without<Downed>>,
+    room_query: Query<(Entity, &Room)>,
+) {
+    // Track the first living player.
+    let (_, player_gt) = rq!(player_query.iter().min_by_key(|(player, _)| player.index));
+    let player_pos = player_gt.translation().xy();
+    let room = room_query
+        .iter()
+        .find(|(_, room)| room.rect.contains(player_pos))
+        .map(|(entity, _)| entity);
+    rq!(current_room.0 != room);
+
+    current_room.0 = room;
+    if let Some(room) = room {
+        commands.entity(room).trigger(OnRoomEnter);
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn apply_room_camera(
+    current_room: Res<CurrentRoom>,
+    camera_root: Res<CameraRoot>,
+    mut camera_query: Query<(
+        &Projection,
+        &mut CameraBounds,
+        &mut CameraLookAhead,
+        &mut CameraZoom,
+    )>,
+    room_query: Query<&Room>,
+) {
+    let (projection, mut bounds, mut look_ahead, mut zoom) =
+        r!(camera_query.get_mut(camera_root.primary));
+    let Some(room) = current_room
+        .0
+        .and_then(|entity| room_query.get(entity).ok())
+    else {
+        bounds.0 = None;
+        look_ahead.distance = 0.0;
+        zoom.target = 1.0;
+        return;
+    };
+
+    bounds.0 = room.camera.clamp.then_some(room.rect);
+    look_ahead.distance = room.camera.look_ahead;
+    zoom.target = room.camera.zoom;
+    if room.camera.fit {
+        let Projection::Orthographic(projection) = projection else {
+            return;
+        };
+        let base_size = projection.area.size() / projection.scale;
+        zoom.target = (room.size / base_size).max_element();
+    }
+}
+
+/// Triggered on a [`Room`] when the player enters it.
+#[derive(Event, Reflect, Debug)]
+pub struct OnRoomEnter;
+
+impl Configure for OnRoomEnter {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Triggered on a [`Room`] when the last enemy that belongs to it is defeated.
+#[derive(Event, Reflect, Debug)]
+pub struct OnRoomClear;
+
+impl Configure for OnRoomClear {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
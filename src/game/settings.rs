@@ -0,0 +1,50 @@
+//! Player-facing gameplay and accessibility options.
+
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(GameplaySettings, AccessibilitySettings)>();
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Show floating numbers for damage dealt.
+    pub damage_numbers: bool,
+    /// Nudge shots aimed with a gamepad stick towards nearby enemies.
+    pub aim_assist: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            damage_numbers: true,
+            aim_assist: true,
+        }
+    }
+}
+
+impl Configure for GameplaySettings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Default, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Keep shooting while the shoot button is held.
+    pub auto_fire: bool,
+    /// Disable full-color sprite flashes.
+    pub reduce_flashing: bool,
+}
+
+impl Configure for AccessibilitySettings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
@@ -0,0 +1,391 @@
+use crate::core::audio::AudioDuck;
+use crate::core::audio::AudioSettings;
+use crate::core::audio::IgnoreDuck;
+use crate::core::audio::sfx_audio;
+use crate::game::actor::combat::damage::OnDamage;
+use crate::game::actor::combat::health::OnDeath;
+use crate::game::actor::combat::health::OnDowned;
+use crate::game::actor::player::Player;
+use crate::prelude::*;
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<SfxConfig>,
+        SfxAssets,
+        SfxVoices,
+        ActiveDucks,
+        PlaySfx,
+    )>();
+}
+
+/// Concurrency limits, priorities and ducking rules for gameplay sound effects.
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SfxConfig {
+    /// The maximum number of sound effects playing at once.
+    pub max_voices: usize,
+    /// The seconds it takes for ducking to fade in or out.
+    pub duck_fade_secs: f32,
+    pub sounds: HashMap<SfxId, SfxRule>,
+}
+
+impl Config for SfxConfig {
+    const FILE: &'static str = "sfx.ron";
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SfxRule {
+    /// The maximum number of voices of this sound playing at once.
+    pub max_voices: usize,
+    /// Voices with a higher priority are kept when the total voice limit is reached.
+    pub priority: i32,
+    /// What to do when `max_voices` is reached.
+    pub steal: VoiceSteal,
+    /// Lower the volume of everything else while this sound plays.
+    #[serde(default)]
+    pub duck: Option<DuckRule>,
+}
+
+impl Default for SfxRule {
+    fn default() -> Self {
+        Self {
+            max_voices: 8,
+            priority: 0,
+            steal: VoiceSteal::Oldest,
+            duck: None,
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum VoiceSteal {
+    /// Stop the oldest voice of the same sound.
+    Oldest,
+    /// Don't play the new sound.
+    Reject,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DuckRule {
+    /// The music volume multiplier.
+    pub music: f32,
+    /// The volume multiplier for other sound effects.
+    pub sfx: f32,
+    /// How long to duck for.
+    pub secs: f32,
+}
+
+#[derive(AssetCollection, Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct SfxAssets {
+    #[asset(path = "audio/sfx/hit.wav")]
+    pub hit: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/shoot.wav")]
+    pub shoot: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/death.wav")]
+    pub death: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/pickup.wav")]
+    pub pickup: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/dash.wav")]
+    pub dash: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/explosion.wav")]
+    pub explosion: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/boss_roar.wav")]
+    pub boss_roar: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/player_death.wav")]
+    pub player_death: Handle<AudioSource>,
+}
+
+impl Configure for SfxAssets {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_collection::<Self>();
+    }
+}
+
+impl SfxAssets {
+    pub fn get(&self, id: SfxId) -> Handle<AudioSource> {
+        match id {
+            SfxId::Hit => &self.hit,
+            SfxId::Shoot => &self.shoot,
+            SfxId::Death => &self.death,
+            SfxId::Pickup => &self.pickup,
+            SfxId::Dash => &self.dash,
+            SfxId::Explosion => &self.explosion,
+            SfxId::BossRoar => &self.boss_roar,
+            SfxId::PlayerDeath => &self.player_death,
+        }
+        .clone()
+    }
+}
+
+/// A gameplay sound effect in [`SfxAssets`].
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SfxId {
+    Hit,
+    Shoot,
+    Death,
+    Pickup,
+    Dash,
+    Explosion,
+    BossRoar,
+    PlayerDeath,
+}
+
+/// The sound effects currently playing, for enforcing [`SfxConfig`] voice limits.
+///
+/// This is tracked in a resource so that sounds triggered in the same frame count too.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct SfxVoices(Vec<SfxVoice>);
+
+impl Configure for SfxVoices {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            forget_finished_voices.in_set(UpdateSystems::SyncEarly),
+        );
+    }
+}
+
+impl SfxVoices {
+    fn stop(&mut self, commands: &mut Commands, index: usize) {
+        let voice = self.0.swap_remove(index);
+        commands.entity(voice.entity).try_despawn();
+    }
+}
+
+#[derive(Reflect, Copy, Clone, Debug)]
+struct SfxVoice {
+    entity: Entity,
+    id: SfxId,
+    priority: i32,
+    started: f32,
+}
+
+fn forget_finished_voices(
+    mut voices: ResMut<SfxVoices>,
+    audio_query: Query<(), With<AudioPlayer>>,
+) {
+    voices.0.retain(|voice| audio_query.contains(voice.entity));
+}
+
+/// The [`DuckRule`]s of the sound effects currently ducking everything else.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct ActiveDucks(Vec<DuckRule>);
+
+impl Configure for ActiveDucks {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(Update, update_audio_duck.in_set(UpdateSystems::SyncLate));
+        app.add_systems(StateFlush, Screen::ANY.on_exit(clear_audio_ducks));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_audio_duck(
+    time: Res<Time>,
+    config: ConfigRef<SfxConfig>,
+    mut active_ducks: ResMut<ActiveDucks>,
+    mut audio_duck: ResMut<AudioDuck>,
+) {
+    let config = r!(config.get());
+    let dt = time.delta_secs();
+    active_ducks.0.retain_mut(|duck| {
+        duck.secs -= dt;
+        duck.secs > 0.0
+    });
+
+    let target = active_ducks
+        .0
+        .iter()
+        .fold(AudioDuck::default(), |target, duck| AudioDuck {
+            music: target.music.min(duck.music),
+            sfx: target.sfx.min(duck.sfx),
+        });
+    let step = dt / config.duck_fade_secs.max(f32::EPSILON);
+    audio_duck.set_if_neq(AudioDuck {
+        music: audio_duck.music + (target.music - audio_duck.music).clamp(-step, step),
+        sfx: audio_duck.sfx + (target.sfx - audio_duck.sfx).clamp(-step, step),
+    });
+}
+
+fn clear_audio_ducks(mut active_ducks: ResMut<ActiveDucks>, mut audio_duck: ResMut<AudioDuck>) {
+    active_ducks.0.clear();
+    *audio_duck = default();
+}
+
+/// An event that plays a gameplay sound effect.
+///
+/// When triggered on an entity without a `position`, the sound plays at the entity's
+/// position. Sounds with a position are panned and attenuated relative to the
+/// [`SpatialListener`], and sounds without one play globally.
+#[derive(Event, Reflect, Copy, Clone, Debug)]
+pub struct PlaySfx {
+    pub id: SfxId,
+    pub position: Option<Vec2>,
+    /// The maximum random change in playback speed, as a fraction.
+    pub pitch_variance: f32,
+}
+
+impl Configure for PlaySfx {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(play_sfx);
+        app.add_observer(play_hit_sfx);
+        app.add_observer(play_death_sfx);
+        app.add_observer(play_player_death_sfx);
+    }
+}
+
+impl PlaySfx {
+    pub fn new(id: SfxId) -> Self {
+        Self {
+            id,
+            position: None,
+            pitch_variance: 0.1,
+        }
+    }
+
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn with_pitch_variance(mut self, pitch_variance: f32) -> Self {
+        self.pitch_variance = pitch_variance;
+        self
+    }
+}
+
+/// Sounds further than this from the [`SpatialListener`] are not played at all.
+const SFX_CUTOFF_DISTANCE: f32 = 900.0;
+
+fn play_sfx(
+    trigger: Trigger<PlaySfx>,
+    mut commands: Commands,
+    time: Res<Time>,
+    config: ConfigRef<SfxConfig>,
+    audio_settings: Res<AudioSettings>,
+    audio_duck: Res<AudioDuck>,
+    sfx_assets: Option<Res<SfxAssets>>,
+    mut voices: ResMut<SfxVoices>,
+    mut active_ducks: ResMut<ActiveDucks>,
+    listener_query: Query<&GlobalTransform, With<SpatialListener>>,
+    gt_query: Query<&GlobalTransform>,
+) {
+    let config = r!(config.get());
+    let sfx_assets = r!(sfx_assets);
+    let position = trigger.position.or_else(|| {
+        let target = trigger.get_target()?;
+        Some(gt_query.get(target).ok()?.translation().xy())
+    });
+    if let Some(position) = position {
+        let listener_pos = r!(listener_query.iter().next()).translation().xy();
+        rq!(position.distance_squared(listener_pos) <= SFX_CUTOFF_DISTANCE.powi(2));
+    }
+    let rule = config.sounds.get(&trigger.id).copied().unwrap_or_default();
+
+    // Enforce the voice limit for this sound.
+    let oldest = voices
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_, voice)| voice.id == trigger.id)
+        .min_by(|(_, a), (_, b)| a.started.total_cmp(&b.started))
+        .map(|(i, _)| i);
+    let count = voices
+        .0
+        .iter()
+        .filter(|voice| voice.id == trigger.id)
+        .count();
+    if count >= rule.max_voices {
+        rq!(rule.steal == VoiceSteal::Oldest);
+        voices.stop(&mut commands, rq!(oldest));
+    }
+
+    // Enforce the total voice limit by stealing the least important voice.
+    if voices.0.len() >= config.max_voices {
+        let index = rq!(voices
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, voice)| voice.priority <= rule.priority)
+            .min_by(|(_, a), (_, b)| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then(a.started.total_cmp(&b.started))
+            })
+            .map(|(i, _)| i));
+        voices.stop(&mut commands, index);
+    }
+
+    let variance = trigger.pitch_variance.abs();
+    let speed = 1.0 + thread_rng().gen_range(-variance..=variance);
+    let volume = if rule.duck.is_some() {
+        1.0
+    } else {
+        audio_duck.sfx
+    };
+    let mut sfx = commands.spawn(sfx_audio(
+        &audio_settings,
+        sfx_assets.get(trigger.id),
+        volume,
+        speed,
+        position,
+    ));
+    if let Some(duck) = rule.duck {
+        sfx.insert(IgnoreDuck);
+        active_ducks.0.push(duck);
+    }
+
+    voices.0.push(SfxVoice {
+        entity: sfx.id(),
+        id: trigger.id,
+        priority: rule.priority,
+        started: time.elapsed_secs(),
+    });
+}
+
+fn play_hit_sfx(
+    trigger: Trigger<OnDamage>,
+    mut commands: Commands,
+    gt_query: Query<&GlobalTransform>,
+) {
+    let target = r!(trigger.get_target());
+    // Damage-over-time ticks would be too noisy.
+    rq!(!trigger.is_tick);
+    let pos = rq!(gt_query.get(target)).translation().xy();
+
+    commands.trigger(PlaySfx::new(SfxId::Hit).at(pos));
+}
+
+fn play_death_sfx(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    gt_query: Query<&GlobalTransform>,
+) {
+    let target = r!(trigger.get_target());
+    let pos = rq!(gt_query.get(target)).translation().xy();
+
+    // The target despawns right after this, so play the sound at its last position.
+    commands.trigger(PlaySfx::new(SfxId::Death).at(pos).with_pitch_variance(0.2));
+}
+
+fn play_player_death_sfx(
+    trigger: Trigger<OnDowned>,
+    mut commands: Commands,
+    player_query: Query<(), With<Player>>,
+) {
+    let target = r!(trigger.get_target());
+    rq!(player_query.contains(target));
+
+    commands.trigger(PlaySfx::new(SfxId::PlayerDeath).with_pitch_variance(0.0));
+}
@@ -0,0 +1,339 @@
+use crate::game::actor::combat::damage::OnDamage;
+use crate::game::actor::combat::health::Health;
+use crate::game::actor::movement::Movement;
+use crate::game::room::Room;
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(RoomTerrain, Terrain)>();
+}
+
+/// Friction multiplier for [`TerrainModifier::slippery`] terrain.
+const SLIPPERY_FRICTION: f32 = 0.15;
+/// Seconds between damage ticks from [`TerrainModifier::damage_per_second`].
+const DAMAGE_INTERVAL: f32 = 0.5;
+
+/// How the ground affects actors standing on it.
+///
+/// Read from the custom properties of Tiled tiles and objects: `friction`, `speed_multiplier`,
+/// `damage_per_second` and `slippery`.
+#[derive(Reflect, Copy, Clone, PartialEq, Debug)]
+pub struct TerrainModifier {
+    /// Multiplies [`Movement::accel`] and [`Movement::decel`].
+    pub friction: f32,
+    /// Multiplies [`Movement::speed`].
+    pub speed_multiplier: f32,
+    pub damage_per_second: f32,
+    /// Drastically reduces friction, like ice.
+    pub slippery: bool,
+}
+
+impl Default for TerrainModifier {
+    fn default() -> Self {
+        Self {
+            friction: 1.0,
+            speed_multiplier: 1.0,
+            damage_per_second: 0.0,
+            slippery: false,
+        }
+    }
+}
+
+impl TerrainModifier {
+    /// Read the terrain properties, if there are any.
+    fn from_properties(properties: &tiled::Properties) -> Option<Self> {
+        let float = |key: &str| match properties.get(key)? {
+            tiled::PropertyValue::FloatValue(x) => Some(*x),
+            tiled::PropertyValue::IntValue(x) => Some(*x as f32),
+            _ => None,
+        };
+        let friction = float("friction");
+        let speed_multiplier = float("speed_multiplier");
+        let damage_per_second = float("damage_per_second");
+        let slippery = match properties.get("slippery") {
+            Some(tiled::PropertyValue::BoolValue(x)) => Some(*x),
+            _ => None,
+        };
+        if friction.is_none()
+            && speed_multiplier.is_none()
+            && damage_per_second.is_none()
+            && slippery.is_none()
+        {
+            return None;
+        }
+
+        Some(Self {
+            friction: friction.unwrap_or(1.0),
+            speed_multiplier: speed_multiplier.unwrap_or(1.0),
+            damage_per_second: damage_per_second.unwrap_or(0.0),
+            slippery: slippery.unwrap_or(false),
+        })
+    }
+
+    /// Stack two modifiers, e.g. from overlapping layers.
+    fn combine(self, other: Self) -> Self {
+        Self {
+            friction: self.friction * other.friction,
+            speed_multiplier: self.speed_multiplier * other.speed_multiplier,
+            damage_per_second: self.damage_per_second + other.damage_per_second,
+            slippery: self.slippery || other.slippery,
+        }
+    }
+
+    /// Apply the modifier to movement tuning.
+    pub fn apply(&self, movement: &Movement) -> Movement {
+        let friction = if self.slippery {
+            self.friction * SLIPPERY_FRICTION
+        } else {
+            self.friction
+        };
+
+        Movement {
+            accel: movement.accel * friction,
+            decel: movement.decel * friction,
+            speed: movement.speed * self.speed_multiplier,
+            ..*movement
+        }
+    }
+}
+
+/// The [`TerrainModifier`]s within a [`Room`], in Tiled pixel coordinates (y-down).
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct RoomTerrain {
+    /// Modifiers from tile properties, by tile position.
+    pub tiles: HashMap<UVec2, TerrainModifier>,
+    /// Modifiers from object properties, by object bounds.
+    pub zones: Vec<(Rect, TerrainModifier)>,
+}
+
+impl Configure for RoomTerrain {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, insert_room_terrain.in_set(UpdateSystems::SyncEarly));
+    }
+}
+
+impl RoomTerrain {
+    /// Look up the modifier at a world position inside `room`.
+    fn get(&self, room: &Room, pos: Vec2) -> Option<TerrainModifier> {
+        let pos = vec2(pos.x - room.rect.min.x, room.rect.max.y - pos.y);
+        let tile = (pos / room.tile_size).floor().as_uvec2();
+        self.zones
+            .iter()
+            .filter(|(rect, _)| rect.contains(pos))
+            .map(|&(_, modifier)| modifier)
+            .chain(self.tiles.get(&tile).copied())
+            .reduce(TerrainModifier::combine)
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn insert_room_terrain(
+    mut commands: Commands,
+    map_assets: Res<Assets<TiledMap>>,
+    room_query: Query<(Entity, &TiledMapHandle), Added<Room>>,
+) {
+    for (entity, handle) in &room_query {
+        let map = &c!(map_assets.get(&handle.0)).map;
+        let mut terrain = RoomTerrain::default();
+
+        for layer in map.layers() {
+            match layer.layer_type() {
+                tiled::LayerType::Tiles(tiles) => {
+                    for y in 0..map.height {
+                        for x in 0..map.width {
+                            let modifier = c!(tiles
+                                .get_tile(x as i32, y as i32)
+                                .and_then(|tile| tile.get_tile())
+                                .and_then(|tile| TerrainModifier::from_properties(
+                                    &tile.properties
+                                )));
+                            terrain
+                                .tiles
+                                .entry(uvec2(x, y))
+                                .and_modify(|x| *x = x.combine(modifier))
+                                .or_insert(modifier);
+                        }
+                    }
+                },
+                tiled::LayerType::Objects(objects) => {
+                    for object in objects.objects() {
+                        let modifier = c!(TerrainModifier::from_properties(&object.properties));
+                        let size = match object.shape {
+                            tiled::ObjectShape::Rect { width, height }
+                            | tiled::ObjectShape::Ellipse { width, height } => vec2(width, height),
+                            _ => continue,
+                        };
+                        let min = vec2(object.x, object.y);
+                        terrain
+                            .zones
+                            .push((Rect::from_corners(min, min + size), modifier));
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        cq!(!terrain.tiles.is_empty() || !terrain.zones.is_empty());
+        commands.entity(entity).insert(terrain);
+    }
+}
+
+/// The [`TerrainModifier`] under an actor.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Terrain {
+    pub modifier: TerrainModifier,
+    /// Seconds until the next damage tick.
+    damage_cooldown: f32,
+}
+
+impl Configure for Terrain {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                update_terrain
+                    .in_set(UpdateSystems::SyncEarly)
+                    .after(insert_room_terrain)
+                    .run_if(Pause::is_disabled),
+                damage_from_terrain
+                    .in_set(UpdateSystems::Update)
+                    .run_if(Pause::is_disabled),
+            ),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_terrain(
+    mut commands: Commands,
+    room_query: Query<(&Room, &RoomTerrain)>,
+    mut actor_query: Query<(Entity, &GlobalTransform, Option<&mut Terrain>), With<Health>>,
+) {
+    for (entity, gt, terrain) in &mut actor_query {
+        let pos = gt.translation().xy();
+        let modifier = room_query
+            .iter()
+            .find(|(room, _)| room.rect.contains(pos))
+            .and_then(|(room, room_terrain)| room_terrain.get(room, pos))
+            .unwrap_or_default();
+
+        if let Some(mut terrain) = terrain {
+            if terrain.modifier != modifier {
+                terrain.modifier = modifier;
+            }
+        } else if modifier != TerrainModifier::default() {
+            commands.entity(entity).insert(Terrain {
+                modifier,
+                damage_cooldown: 0.0,
+            });
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn damage_from_terrain(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut terrain_query: Query<(Entity, &mut Terrain)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut terrain) in &mut terrain_query {
+        terrain.damage_cooldown -= dt;
+        if terrain.modifier.damage_per_second <= 0.0 {
+            terrain.damage_cooldown = terrain.damage_cooldown.max(0.0);
+            continue;
+        }
+        cq!(terrain.damage_cooldown <= 0.0);
+
+        terrain.damage_cooldown += DAMAGE_INTERVAL;
+        commands.entity(entity).trigger(OnDamage::new(
+            terrain.modifier.damage_per_second * DAMAGE_INTERVAL,
+            None,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::room::RoomCamera;
+    use crate::game::room::RoomKind;
+
+    fn room() -> Room {
+        Room {
+            kind: RoomKind::Medium,
+            key: IVec2::ZERO,
+            tile_size: vec2(32.0, 32.0),
+            size: vec2(960.0, 640.0),
+            rect: Rect::new(100.0, 200.0, 1060.0, 840.0),
+            camera: RoomCamera {
+                zoom: 1.0,
+                fit: false,
+                look_ahead: 0.0,
+                clamp: true,
+            },
+            enemies: 0,
+            cleared: false,
+        }
+    }
+
+    fn mud() -> TerrainModifier {
+        TerrainModifier {
+            friction: 0.5,
+            speed_multiplier: 0.6,
+            ..default()
+        }
+    }
+
+    fn spikes() -> TerrainModifier {
+        TerrainModifier {
+            damage_per_second: 20.0,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_combine() {
+        let ice = TerrainModifier {
+            friction: 0.8,
+            slippery: true,
+            ..default()
+        };
+        let combined = mud().combine(ice).combine(spikes()).combine(spikes());
+        assert!((combined.friction - 0.4).abs() < 1e-4);
+        assert!((combined.speed_multiplier - 0.6).abs() < 1e-4);
+        assert_eq!(combined.damage_per_second, 40.0);
+        assert!(combined.slippery);
+        assert_eq!(mud().combine(TerrainModifier::default()), mud());
+    }
+
+    #[test]
+    fn test_get_tile_is_y_down() {
+        let room = room();
+        let terrain = RoomTerrain {
+            tiles: HashMap::from([(uvec2(1, 0), mud())]),
+            ..default()
+        };
+        // Tile (1, 0) is the second tile of the top row.
+        assert_eq!(terrain.get(&room, vec2(150.0, 830.0)), Some(mud()));
+        assert_eq!(terrain.get(&room, vec2(120.0, 830.0)), None);
+        assert_eq!(terrain.get(&room, vec2(150.0, 210.0)), None);
+    }
+
+    #[test]
+    fn test_get_combines_zones_and_tile() {
+        let room = room();
+        let terrain = RoomTerrain {
+            tiles: HashMap::from([(uvec2(0, 0), mud())]),
+            zones: vec![(Rect::new(0.0, 0.0, 64.0, 64.0), spikes())],
+        };
+        let both = mud().combine(spikes());
+        assert_eq!(terrain.get(&room, vec2(110.0, 830.0)), Some(both));
+        assert_eq!(terrain.get(&room, vec2(150.0, 800.0)), Some(spikes()));
+        assert_eq!(terrain.get(&room, vec2(200.0, 700.0)), None);
+    }
+}
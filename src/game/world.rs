@@ -1,14 +1,33 @@
 use crate::game::GameLayer;
 use crate::game::actor::ActorAssets;
 use crate::game::actor::camera_cutie::{CameraCutieEvent, send_camera_follow_event};
+use crate::game::actor::combat::shield::Armor;
 use crate::game::actor::enemy::{get_enemy, get_enemy_aseprite};
 use crate::game::actor::movement::spring::mass_spring_damper;
 use crate::game::actor::player::get_player;
+use crate::game::controls::ControlsSettings;
 use crate::prelude::*;
 use crate::screen::Screen;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(LevelAssets, Level)>();
+    app.configure::<(LevelAssets, Level, RunSeed)>();
+}
+
+/// The seed for content rolled during the current run, such as shop inventories.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct RunSeed(pub u64);
+
+impl Configure for RunSeed {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Gameplay.on_enter(reroll_run_seed));
+    }
+}
+
+fn reroll_run_seed(mut run_seed: ResMut<RunSeed>) {
+    run_seed.0 = thread_rng().next_u64();
 }
 
 #[derive(AssetCollection, Resource, Reflect, Default, Debug)]
@@ -53,6 +72,7 @@ pub fn spawn_world(
     _world: NextRef<Level>,
     world_assets: Res<LevelAssets>,
     actor_assets: Res<ActorAssets>,
+    controls: Res<ControlsSettings>,
     set_camera_event: EventWriter<CameraCutieEvent>,
 ) {
     commands.spawn((
@@ -65,7 +85,7 @@ pub fn spawn_world(
     ));
 
     let player_spawn_commands = commands.spawn((
-        get_player(actor_assets.rat_handle.clone()),
+        get_player(actor_assets.rat_handle.clone(), &controls, 0, None),
         Transform::from_xyz(475., 330., 5.),
         DespawnOnExitState::<Level>::default(),
     ));
@@ -83,7 +103,7 @@ pub fn spawn_world(
         get_enemy_aseprite(
             "Blob Cannon",
             actor_assets.cannon.clone(),
-            "Idle",
+            "blob_cannon",
             100.,
             32.,
         ),
@@ -95,30 +115,19 @@ pub fn spawn_world(
         get_enemy_aseprite(
             "Mouse Boss",
             actor_assets.mouse_boss.clone(),
-            "Move Down",
+            "mouse_boss",
             400.,
             96.,
         ),
+        Armor::percent(0.25),
         Transform::from_xyz(200., 600., 5.),
         DespawnOnExitState::<Screen>::Recursive,
     ));
 
     commands.spawn((
-        get_enemy_aseprite("Mouse", actor_assets.mouse.clone(), "move down", 25., 32.),
+        get_enemy_aseprite("Mouse", actor_assets.mouse.clone(), "mouse", 25., 32.),
         Transform::from_xyz(200., 100., 5.),
         DespawnOnExitState::<Screen>::Recursive,
     ));
-
-    commands.spawn((
-        get_enemy_aseprite(
-            "Cheese Item",
-            actor_assets.exp_cheese.clone(),
-            "Frame",
-            15.,
-            16.,
-        ),
-        Transform::from_xyz(400., 463., 5.),
-        DespawnOnExitState::<Screen>::Recursive,
-    ));
 }
 pub fn despawn() {}
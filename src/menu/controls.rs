@@ -0,0 +1,323 @@
+use crate::game::controls::ControlAction;
+use crate::game::controls::ControlSlot;
+use crate::game::controls::ControlsSettings;
+use crate::game::controls::InputBinding;
+use crate::menu::Menu;
+use crate::menu::MenuAction;
+use crate::menu::MenuRoot;
+use crate::prelude::*;
+use crate::screen::gameplay::GameplayAction;
+use crate::theme::focus::FocusAction;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        StateFlush,
+        Menu::SettingsControls.on_edge(reset_rebinding, spawn_controls_menu),
+    );
+
+    app.configure::<(
+        ControlsView,
+        SettingBinding<ControlsView>,
+        Rebinding,
+        IsBindingButton,
+        IsRebindingStatus,
+    )>();
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_controls_menu(mut commands: Commands, menu_root: Res<MenuRoot>) {
+    commands
+        .entity(menu_root.ui)
+        .with_child(widget::body(children![
+            widget::header("[b]Controls"),
+            (
+                Name::new("Device"),
+                Node {
+                    column_gap: Vw(6.0),
+                    ..Node::ROW_CENTER
+                },
+                children![
+                    widget::label("Device"),
+                    widget::setting_selector(SettingBinding::<ControlsView>::cycle(
+                        "slot",
+                        |slot| match slot {
+                            "Keyboard" => "Keyboard & mouse".to_string(),
+                            slot => slot.to_string(),
+                        },
+                    )),
+                ],
+            ),
+            (
+                Name::new("Bindings"),
+                Node {
+                    margin: UiRect::vertical(Vw(3.0)),
+                    column_gap: Vw(6.0),
+                    align_items: AlignItems::Start,
+                    ..Node::ROW_CENTER
+                },
+                children![
+                    grid(&ControlAction::MOVEMENT),
+                    grid(&ControlAction::BUTTONS),
+                ],
+            ),
+            (widget::label(""), IsRebindingStatus),
+            widget::row_of_buttons(children![
+                widget::wide_button("Reset to defaults", reset_controls),
+                widget::wide_button("Back", go_back),
+            ]),
+        ]));
+}
+
+fn grid(actions: &'static [ControlAction]) -> impl Bundle {
+    (
+        Name::new("Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Vw(1.0),
+            column_gap: Vw(2.0),
+            grid_template_columns: RepeatedGridTrack::auto(2),
+            ..default()
+        },
+        GridAlignment::columns([JustifySelf::End, JustifySelf::Start]),
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            for &action in actions {
+                parent.spawn(widget::label(action.label()));
+                parent.spawn((
+                    widget::compact_button("", start_rebinding),
+                    IsBindingButton(action),
+                ));
+            }
+        })),
+    )
+}
+
+fn reset_controls(_: Trigger<Pointer<Click>>, mut controls: ResMut<ControlsSettings>) {
+    *controls = default();
+}
+
+fn go_back(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
+    menu.pop();
+}
+
+/// Which input device's bindings the controls menu is showing.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct ControlsView {
+    pub slot: ControlSlot,
+}
+
+impl Configure for ControlsView {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+/// The binding currently waiting for input in the controls menu.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct Rebinding {
+    pub target: Option<(ControlAction, ControlSlot)>,
+    pub elapsed: f32,
+    /// A message to show below the bindings, like a resolved conflict.
+    pub status: String,
+}
+
+impl Configure for Rebinding {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            Menu::SettingsControls.on_update((
+                // Capture input before it can be read as a menu action.
+                capture_rebinding.in_set(UpdateSystems::SyncEarly),
+                reset_rebinding
+                    .in_set(UpdateSystems::Update)
+                    .run_if(resource_changed::<ControlsView>),
+            )),
+        );
+    }
+}
+
+/// Give up on rebinding after this many seconds without input.
+const REBINDING_TIMEOUT_SECS: f32 = 5.0;
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn capture_rebinding(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepad_query: Query<&Gamepad>,
+    mut controls: ResMut<ControlsSettings>,
+    mut rebinding: ResMut<Rebinding>,
+    menu_input_map: Res<InputMap<MenuAction>>,
+    mut menu_actions: ResMut<ActionState<MenuAction>>,
+    mut gameplay_actions: ResMut<ActionState<GameplayAction>>,
+    mut focus_actions: ResMut<ActionState<FocusAction>>,
+) {
+    let (action, slot) = rq!(rebinding.target);
+    // Don't navigate the menu while waiting for input, or let the captured input trigger it.
+    menu_actions.consume_all();
+    gameplay_actions.consume_all();
+    focus_actions.consume_all();
+
+    // Ignore the input that started rebinding.
+    let first_frame = rebinding.elapsed == 0.0;
+    rebinding.elapsed += time.delta_secs();
+    rq!(!first_frame);
+    if rebinding.elapsed > REBINDING_TIMEOUT_SECS {
+        *rebinding = default();
+        return;
+    }
+
+    let binding = rq!(match slot {
+        ControlSlot::Keyboard => keyboard
+            .get_just_pressed()
+            .next()
+            .map(|&key| InputBinding::Key(key))
+            // Mouse buttons are captured on release, after the click that started rebinding.
+            .or_else(|| {
+                mouse
+                    .get_just_released()
+                    .next()
+                    .map(|&button| InputBinding::Mouse(button))
+            }),
+        ControlSlot::Gamepad => gamepad_query
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next())
+            .map(|&button| InputBinding::Gamepad(button)),
+    });
+    if cancels_rebinding(action, slot, binding, &menu_input_map) {
+        *rebinding = default();
+        return;
+    }
+    rq!(action.accepts(binding));
+
+    *rebinding = Rebinding {
+        status: controls.bind(action, binding).message(),
+        ..default()
+    };
+}
+
+/// Whether `binding` cancels rebinding instead of being bound to `action`.
+///
+/// The menu's back input cancels, unless it's the action's default (like Escape for Pause),
+/// so that the default can still be restored.
+fn cancels_rebinding(
+    action: ControlAction,
+    slot: ControlSlot,
+    binding: InputBinding,
+    menu_input_map: &InputMap<MenuAction>,
+) -> bool {
+    binding.is_bound_to(menu_input_map, &MenuAction::Back)
+        && ControlsSettings::default().get(action, slot) != Some(binding)
+}
+
+fn reset_rebinding(mut rebinding: ResMut<Rebinding>) {
+    *rebinding = default();
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct IsBindingButton(ControlAction);
+
+impl Configure for IsBindingButton {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Menu::SettingsControls.on_update(
+                update_binding_buttons
+                    .in_set(UpdateSystems::SyncLate)
+                    .run_if(
+                        resource_changed::<ControlsSettings>
+                            .or(resource_changed::<ControlsView>)
+                            .or(resource_changed::<Rebinding>)
+                            .or(any_match_filter::<Added<IsBindingButton>>),
+                    ),
+            ),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_binding_buttons(
+    controls: Res<ControlsSettings>,
+    view: Res<ControlsView>,
+    rebinding: Res<Rebinding>,
+    button_query: Query<(&IsBindingButton, &Children)>,
+    mut text_query: Query<&mut RichText>,
+) {
+    for (button, children) in &button_query {
+        let label = *c!(children.first());
+        let text = if rebinding.target == Some((button.0, view.slot)) {
+            "...".to_string()
+        } else {
+            controls
+                .get(button.0, view.slot)
+                .map_or_else(|| "-".to_string(), InputBinding::label)
+        };
+        c!(text_query.get_mut(label)).sections = parse_rich(text);
+    }
+}
+
+fn start_rebinding(
+    trigger: Trigger<Pointer<Click>>,
+    view: Res<ControlsView>,
+    mut rebinding: ResMut<Rebinding>,
+    menu_input_map: Res<InputMap<MenuAction>>,
+    button_query: Query<&IsBindingButton>,
+) {
+    let target = r!(trigger.get_target());
+    let button = r!(button_query.get(target));
+    rq!(rebinding.target.is_none());
+
+    let (prompt, cancel) = match view.slot {
+        ControlSlot::Keyboard => (
+            "Press a key or mouse button",
+            InputBinding::Key(KeyCode::Escape),
+        ),
+        ControlSlot::Gamepad => (
+            "Press a gamepad button",
+            InputBinding::Gamepad(GamepadButton::East),
+        ),
+    };
+    *rebinding = Rebinding {
+        target: Some((button.0, view.slot)),
+        elapsed: 0.0,
+        status: if cancels_rebinding(button.0, view.slot, cancel, &menu_input_map) {
+            format!("{prompt}, or {} to cancel", cancel.label())
+        } else {
+            prompt.to_string()
+        },
+    };
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct IsRebindingStatus;
+
+impl Configure for IsRebindingStatus {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Menu::SettingsControls.on_update(
+                update_rebinding_status
+                    .in_set(UpdateSystems::SyncLate)
+                    .run_if(resource_changed::<Rebinding>),
+            ),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_rebinding_status(
+    rebinding: Res<Rebinding>,
+    mut status_query: Query<&mut RichText, With<IsRebindingStatus>>,
+) {
+    for mut text in &mut status_query {
+        text.sections = parse_rich(&rebinding.status);
+    }
+}
@@ -1,3 +1,4 @@
+mod controls;
 mod intro;
 mod main;
 mod pause;
@@ -46,6 +47,10 @@ pub enum Menu {
     Intro,
     Pause,
     Settings,
+    SettingsAudio,
+    SettingsVideo,
+    SettingsGameplay,
+    SettingsControls,
 }
 
 impl Configure for Menu {
@@ -59,7 +64,13 @@ impl Configure for Menu {
                 Menu::ANY.on_disable(Pause::disable),
             ),
         );
-        app.add_plugins((main::plugin, intro::plugin, pause::plugin, settings::plugin));
+        app.add_plugins((
+            main::plugin,
+            intro::plugin,
+            pause::plugin,
+            settings::plugin,
+            controls::plugin,
+        ));
     }
 }
 
@@ -73,7 +84,7 @@ impl Configure for MenuAction {
         app.init_resource::<ActionState<Self>>();
         app.insert_resource(
             InputMap::default()
-                .with(Self::Back, GamepadButton::South)
+                .with(Self::Back, GamepadButton::East)
                 .with(Self::Back, KeyCode::Escape),
         );
         app.add_plugins(InputManagerPlugin::<Self>::default());
@@ -1,19 +1,33 @@
-use bevy_simple_prefs::Prefs;
-use bevy_simple_prefs::PrefsPlugin;
-
 use crate::core::audio::AudioSettings;
+use crate::core::camera::CameraSettings;
+use crate::core::window::VideoSettings;
+use crate::game::controls::ControlsSettings;
+use crate::game::settings::AccessibilitySettings;
+use crate::game::settings::GameplaySettings;
 use crate::menu::Menu;
 use crate::menu::MenuRoot;
 use crate::prelude::*;
+use crate::util::prefs::load_prefs;
+use crate::util::prefs::save_prefs;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(StateFlush, Menu::Settings.on_enter(spawn_settings_menu));
+    app.add_systems(
+        StateFlush,
+        (
+            Menu::Settings.on_enter(spawn_settings_menu),
+            Menu::SettingsAudio.on_enter(spawn_audio_settings_menu),
+            Menu::SettingsVideo.on_enter(spawn_video_settings_menu),
+            Menu::SettingsGameplay.on_enter(spawn_gameplay_settings_menu),
+        ),
+    );
 
     app.configure::<(
         Settings,
-        IsMasterVolumeSelector,
-        IsMusicVolumeSelector,
-        IsUiVolumeSelector,
+        SettingBinding<AudioSettings>,
+        SettingBinding<VideoSettings>,
+        SettingBinding<CameraSettings>,
+        SettingBinding<GameplaySettings>,
+        SettingBinding<AccessibilitySettings>,
     )>();
 }
 
@@ -23,16 +37,127 @@ fn spawn_settings_menu(mut commands: Commands, menu_root: Res<MenuRoot>) {
         .entity(menu_root.ui)
         .with_child(widget::body(children![
             widget::header("[b]Settings"),
-            grid(),
-            widget::row_of_buttons(children![widget::wide_button("Back", go_back)]),
+            widget::column_of_buttons(children![
+                widget::wide_button("Audio", open_audio_settings),
+                widget::wide_button("Video", open_video_settings),
+                widget::wide_button("Gameplay", open_gameplay_settings),
+                widget::wide_button("Controls", open_controls_settings),
+                widget::wide_button("Back", go_back),
+            ]),
         ]));
 }
 
+fn open_audio_settings(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
+    menu.push(Menu::SettingsAudio);
+}
+
+fn open_video_settings(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
+    menu.push(Menu::SettingsVideo);
+}
+
+fn open_gameplay_settings(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
+    menu.push(Menu::SettingsGameplay);
+}
+
+fn open_controls_settings(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
+    menu.push(Menu::SettingsControls);
+}
+
 fn go_back(_: Trigger<Pointer<Click>>, mut menu: ResMut<NextStateStack<Menu>>) {
     menu.pop();
 }
 
-fn grid() -> impl Bundle {
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_audio_settings_menu(mut commands: Commands, menu_root: Res<MenuRoot>) {
+    commands.entity(menu_root.ui).with_child(settings_page(
+        "[b]Audio",
+        children![
+            widget::label("Master volume"),
+            widget::setting_selector(SettingBinding::<AudioSettings>::percent("master_volume")),
+            widget::label("Music volume"),
+            widget::setting_selector(SettingBinding::<AudioSettings>::percent("music_volume")),
+            widget::label("UI volume"),
+            widget::setting_selector(SettingBinding::<AudioSettings>::percent("ui_volume")),
+            widget::label("SFX volume"),
+            widget::setting_selector(SettingBinding::<AudioSettings>::percent("sfx_volume")),
+        ],
+    ));
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_video_settings_menu(mut commands: Commands, menu_root: Res<MenuRoot>) {
+    commands.entity(menu_root.ui).with_child(settings_page(
+        "[b]Video",
+        children![
+            widget::label("Window mode"),
+            widget::setting_selector(SettingBinding::<VideoSettings>::cycle(
+                "window_mode",
+                str::to_string,
+            )),
+            widget::label("VSync"),
+            widget::setting_selector(SettingBinding::<VideoSettings>::toggle(
+                "vsync", "Off", "On",
+            )),
+            widget::label("Pixel-perfect"),
+            widget::setting_selector(SettingBinding::<VideoSettings>::toggle(
+                "pixel_perfect",
+                "Off",
+                "On",
+            )),
+        ],
+    ));
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_gameplay_settings_menu(mut commands: Commands, menu_root: Res<MenuRoot>) {
+    commands.entity(menu_root.ui).with_child(settings_page(
+        "[b]Gameplay",
+        children![
+            widget::label("Screen shake"),
+            widget::setting_selector(SettingBinding::<CameraSettings>::toggle(
+                "shake_enabled",
+                "Off",
+                "On",
+            )),
+            widget::label("Shake intensity"),
+            widget::setting_selector(SettingBinding::<CameraSettings>::percent("shake_intensity")),
+            widget::label("Damage numbers"),
+            widget::setting_selector(SettingBinding::<GameplaySettings>::toggle(
+                "damage_numbers",
+                "Off",
+                "On",
+            )),
+            widget::label("Aim assist"),
+            widget::setting_selector(SettingBinding::<GameplaySettings>::toggle(
+                "aim_assist",
+                "Off",
+                "On",
+            )),
+            widget::label("Auto-fire"),
+            widget::setting_selector(SettingBinding::<AccessibilitySettings>::toggle(
+                "auto_fire",
+                "Off",
+                "On",
+            )),
+            widget::label("Reduce flashing"),
+            widget::setting_selector(SettingBinding::<AccessibilitySettings>::toggle(
+                "reduce_flashing",
+                "Off",
+                "On",
+            )),
+        ],
+    ));
+}
+
+fn settings_page(title: &'static str, grid_children: impl Bundle) -> impl Bundle {
+    widget::body(children![
+        widget::header(title),
+        grid(grid_children),
+        widget::row_of_buttons(children![widget::wide_button("Back", go_back)]),
+    ])
+}
+
+fn grid(children: impl Bundle) -> impl Bundle {
     (
         Name::new("Grid"),
         Node {
@@ -47,181 +172,65 @@ fn grid() -> impl Bundle {
             ..default()
         },
         GridAlignment::columns([JustifySelf::End, JustifySelf::Start]),
-        children![
-            widget::label("Master volume"),
-            widget::selector(IsMasterVolumeSelector, master_volume_down, master_volume_up),
-            widget::label("Music volume"),
-            widget::selector(IsMusicVolumeSelector, music_volume_down, music_volume_up),
-            widget::label("UI volume"),
-            widget::selector(IsUiVolumeSelector, ui_volume_down, ui_volume_up),
-        ],
+        children,
     )
 }
 
-#[derive(Component, Reflect, Debug)]
-#[reflect(Component)]
-struct IsMasterVolumeSelector;
-
-impl Configure for IsMasterVolumeSelector {
-    fn configure(app: &mut App) {
-        app.register_type::<Self>();
-        app.add_systems(
-            Update,
-            Menu::Settings.on_update(update_master_volume_selector.in_set(UpdateSystems::Update)),
-        );
-    }
-}
-
-#[cfg_attr(feature = "native_dev", hot)]
-fn update_master_volume_selector(
-    audio_settings: Res<AudioSettings>,
-    selector_query: Query<Entity, With<IsMasterVolumeSelector>>,
-    children_query: Query<&Children>,
-    mut text_query: Query<&mut RichText>,
-    mut disabled_query: Query<&mut InteractionDisabled>,
-) {
-    for entity in &selector_query {
-        let children = c!(children_query.get(entity))
-            .into_iter()
-            .collect::<Vec<_>>();
-
-        let left = **c!(children.first());
-        c!(disabled_query.get_mut(left)).0 = audio_settings.master_volume <= f32::EPSILON;
-
-        let mid = **c!(children.get(1));
-        let mid_children = c!(children_query.get(mid));
-        let label = *c!(mid_children.first());
-        c!(text_query.get_mut(label)).sections =
-            parse_rich(format!("{:.0}%", audio_settings.master_volume * 100.0));
-
-        let right = **c!(children.get(2));
-        c!(disabled_query.get_mut(right)).0 = audio_settings.master_volume >= 1.0 - f32::EPSILON;
-    }
-}
-
-fn master_volume_down(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.master_volume = (audio_settings.master_volume - 0.1).max(0.0);
-}
-
-fn master_volume_up(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.master_volume = (audio_settings.master_volume + 0.1).min(1.0);
+/// All persistent settings.
+///
+/// Add new settings resources here so that they're saved.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Settings {
+    audio_settings: AudioSettings,
+    camera_settings: CameraSettings,
+    video_settings: VideoSettings,
+    controls_settings: ControlsSettings,
+    gameplay_settings: GameplaySettings,
+    accessibility_settings: AccessibilitySettings,
 }
 
-#[derive(Component, Reflect, Debug)]
-#[reflect(Component)]
-struct IsMusicVolumeSelector;
-
-impl Configure for IsMusicVolumeSelector {
+impl Configure for Settings {
     fn configure(app: &mut App) {
-        app.register_type::<Self>();
-        app.add_systems(
-            Update,
-            Menu::Settings.on_update(update_music_volume_selector.in_set(UpdateSystems::Update)),
-        );
-    }
-}
-
-#[cfg_attr(feature = "native_dev", hot)]
-fn update_music_volume_selector(
-    audio_settings: Res<AudioSettings>,
-    selector_query: Query<Entity, With<IsMusicVolumeSelector>>,
-    children_query: Query<&Children>,
-    mut text_query: Query<&mut RichText>,
-    mut disabled_query: Query<&mut InteractionDisabled>,
-) {
-    for entity in &selector_query {
-        let children = c!(children_query.get(entity))
-            .into_iter()
-            .collect::<Vec<_>>();
-
-        let left = **c!(children.first());
-        c!(disabled_query.get_mut(left)).0 = audio_settings.music_volume <= f32::EPSILON;
-
-        let mid = **c!(children.get(1));
-        let mid_children = c!(children_query.get(mid));
-        let label = *c!(mid_children.first());
-        c!(text_query.get_mut(label)).sections =
-            parse_rich(format!("{:.0}%", audio_settings.music_volume * 100.0));
-
-        let right = **c!(children.get(2));
-        c!(disabled_query.get_mut(right)).0 = audio_settings.music_volume >= 1.0 - f32::EPSILON;
+        app.add_systems(Startup, load_prefs::<Self>);
+        app.add_systems(Last, save_prefs::<Self>);
     }
 }
 
-fn music_volume_down(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.music_volume = (audio_settings.music_volume - 0.1).max(0.0);
-}
-
-fn music_volume_up(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.music_volume = (audio_settings.music_volume + 0.1).min(1.0);
-}
+impl Prefs for Settings {
+    const FILE: &'static str = "settings.ron";
+    const VERSION: u32 = 1;
 
-#[derive(Component, Reflect, Debug)]
-#[reflect(Component)]
-struct IsUiVolumeSelector;
+    fn gather(world: &World) -> Self {
+        fn get<R: Resource + Clone + Default>(world: &World) -> R {
+            world.get_resource::<R>().cloned().unwrap_or_default()
+        }
 
-impl Configure for IsUiVolumeSelector {
-    fn configure(app: &mut App) {
-        app.register_type::<Self>();
-        app.add_systems(
-            Update,
-            Menu::Settings.on_update(update_ui_volume_selector.in_set(UpdateSystems::Update)),
-        );
+        Self {
+            audio_settings: get(world),
+            camera_settings: get(world),
+            video_settings: get(world),
+            controls_settings: get(world),
+            gameplay_settings: get(world),
+            accessibility_settings: get(world),
+        }
     }
-}
 
-#[cfg_attr(feature = "native_dev", hot)]
-fn update_ui_volume_selector(
-    audio_settings: Res<AudioSettings>,
-    selector_query: Query<Entity, With<IsUiVolumeSelector>>,
-    children_query: Query<&Children>,
-    mut text_query: Query<&mut RichText>,
-    mut disabled_query: Query<&mut InteractionDisabled>,
-) {
-    for entity in &selector_query {
-        let children = c!(children_query.get(entity))
-            .into_iter()
-            .collect::<Vec<_>>();
-
-        let left = **c!(children.first());
-        c!(disabled_query.get_mut(left)).0 = audio_settings.ui_volume <= f32::EPSILON;
-
-        let mid = **c!(children.get(1));
-        let mid_children = c!(children_query.get(mid));
-        let label = *c!(mid_children.first());
-        c!(text_query.get_mut(label)).sections =
-            parse_rich(format!("{:.0}%", audio_settings.ui_volume * 100.0));
-
-        let right = **c!(children.get(2));
-        c!(disabled_query.get_mut(right)).0 = audio_settings.ui_volume >= 1.0 - f32::EPSILON;
+    fn apply(self, world: &mut World) {
+        world.insert_resource(self.audio_settings);
+        world.insert_resource(self.camera_settings);
+        world.insert_resource(self.video_settings);
+        world.insert_resource(self.controls_settings);
+        world.insert_resource(self.gameplay_settings);
+        world.insert_resource(self.accessibility_settings);
     }
-}
-
-fn ui_volume_down(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.ui_volume = (audio_settings.ui_volume - 0.1).max(0.0);
-}
 
-fn ui_volume_up(_: Trigger<Pointer<Click>>, mut audio_settings: ResMut<AudioSettings>) {
-    audio_settings.ui_volume = (audio_settings.ui_volume + 0.1).min(1.0);
-}
-
-#[derive(Prefs, Reflect, Default)]
-struct Settings {
-    pub audio_settings: AudioSettings,
-}
-
-impl Configure for Settings {
-    fn configure(app: &mut App) {
-        app.add_plugins(PrefsPlugin::<Settings> {
-            #[cfg(feature = "native")]
-            path: {
-                let path = r!(dirs::config_local_dir()).join(env!("CARGO_PKG_NAME"));
-                // Create parent directories if necessary.
-                r!(std::fs::create_dir_all(&path).is_ok());
-                r!(std::fs::exists(&path));
-                path.join("settings.ron")
-            },
-            ..default()
-        });
+    fn is_changed(world: &World) -> bool {
+        world.is_resource_changed::<AudioSettings>()
+            || world.is_resource_changed::<CameraSettings>()
+            || world.is_resource_changed::<VideoSettings>()
+            || world.is_resource_changed::<ControlsSettings>()
+            || world.is_resource_changed::<GameplaySettings>()
+            || world.is_resource_changed::<AccessibilitySettings>()
     }
 }
@@ -1,6 +1,9 @@
-use crate::core::audio::AudioSettings;
-use crate::core::audio::music_audio;
+mod hud;
+
 use crate::game::actor::ActorAssets;
+use crate::game::hazard::HazardAssets;
+use crate::game::item::ItemAssets;
+use crate::game::sfx::SfxAssets;
 use crate::game::world::{Level, LevelAssets};
 use crate::menu::Menu;
 use crate::prelude::*;
@@ -9,45 +12,20 @@ use crate::screen::Screen;
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         StateFlush,
-        Screen::Gameplay.on_edge(
-            Level::disable,
-            (spawn_gameplay_screen, (Level(1).enter(), Level::trigger)),
-        ),
+        Screen::Gameplay.on_edge(Level::disable, (Level(1).enter(), Level::trigger)),
     );
 
-    app.configure::<(GameplayAssets, GameplayAction)>();
-}
-
-fn spawn_gameplay_screen(
-    mut commands: Commands,
-    audio_settings: Res<AudioSettings>,
-    assets: Res<GameplayAssets>,
-) {
-    commands.spawn((
-        music_audio(&audio_settings, assets.music.clone()),
-        DespawnOnExitState::<Screen>::Recursive,
-    ));
+    app.configure::<GameplayAction>();
+    app.add_plugins(hud::plugin);
 }
 
 pub fn load_collections(state: LoadingState<BevyState<Screen>>) -> LoadingState<BevyState<Screen>> {
     state
-        .load_collection::<GameplayAssets>()
         .load_collection::<LevelAssets>()
         .load_collection::<ActorAssets>()
-}
-
-#[derive(AssetCollection, Resource, Reflect, Default)]
-#[reflect(Resource)]
-pub struct GameplayAssets {
-    #[asset(path = "audio/music/summer.ogg")]
-    music: Handle<AudioSource>,
-}
-
-impl Configure for GameplayAssets {
-    fn configure(app: &mut App) {
-        app.register_type::<Self>();
-        app.init_collection::<Self>();
-    }
+        .load_collection::<ItemAssets>()
+        .load_collection::<HazardAssets>()
+        .load_collection::<SfxAssets>()
 }
 
 #[derive(Actionlike, Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -60,13 +38,8 @@ impl Configure for GameplayAction {
     fn configure(app: &mut App) {
         app.init_resource::<ActionState<Self>>();
         app.add_plugins(InputManagerPlugin::<Self>::default());
-        app.insert_resource(
-            InputMap::default()
-                .with(Self::Pause, GamepadButton::Start)
-                .with(Self::Pause, KeyCode::Escape)
-                .with(Self::Pause, KeyCode::KeyP)
-                .with(Self::CloseMenu, KeyCode::KeyP),
-        );
+        // Bindings are applied from `ControlsSettings`.
+        app.init_resource::<InputMap<Self>>();
         app.add_systems(
             Update,
             Screen::Gameplay.on_update((
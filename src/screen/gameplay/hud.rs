@@ -0,0 +1,352 @@
+//! The heads-up display, with a panel for each player.
+
+use crate::game::actor::combat::damage::DamageKind;
+use crate::game::actor::combat::health::Downed;
+use crate::game::actor::combat::health::Health;
+use crate::game::actor::combat::shield::Shield;
+use crate::game::actor::experience::Experience;
+use crate::game::actor::movement::input::{Dash, ShotCooldown};
+use crate::game::actor::player::Player;
+use crate::game::economy::Wallet;
+use crate::game::item::effects::damage_over_time::ActiveDOT;
+use crate::game::settings::AccessibilitySettings;
+use crate::prelude::*;
+use crate::screen::Screen;
+use crate::screen::ScreenRoot;
+use crate::theme::widget::Meter;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(StateFlush, Screen::Gameplay.on_enter(spawn_hud));
+
+    app.configure::<(IsHud, PlayerHud, HudMeter, HudCurrency, HudStatus)>();
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn spawn_hud(mut commands: Commands, screen_root: Res<ScreenRoot>) {
+    commands.entity(screen_root.ui).with_child((
+        Name::new("Hud"),
+        Node {
+            padding: UiRect::all(Vw(2.0)),
+            row_gap: Vw(1.5),
+            ..Node::COLUMN_LEFT
+        },
+        Pickable::IGNORE,
+        IsHud,
+    ));
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct IsHud;
+
+impl Configure for IsHud {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Gameplay.on_update(
+                (
+                    sync_player_huds,
+                    (update_hud_meters, update_hud_currency, update_hud_status),
+                )
+                    .chain()
+                    .in_set(UpdateSystems::Update),
+            ),
+        );
+    }
+}
+
+/// Spawn a panel for each player that joins, and despawn it when the player is gone.
+#[cfg_attr(feature = "native_dev", hot)]
+fn sync_player_huds(
+    mut commands: Commands,
+    hud_query: Query<Entity, With<IsHud>>,
+    panel_query: Query<(Entity, &PlayerHud)>,
+    player_query: Query<(Entity, &Player)>,
+) {
+    for (panel, hud) in &panel_query {
+        cq!(!player_query.contains(hud.0));
+        commands.entity(panel).despawn();
+    }
+
+    let hud = r!(hud_query.single());
+    let mut players = player_query
+        .iter()
+        .filter(|&(entity, _)| !panel_query.iter().any(|(_, panel)| panel.0 == entity))
+        .collect::<Vec<_>>();
+    players.sort_by_key(|(_, player)| player.index);
+    for (entity, player) in players {
+        commands
+            .entity(hud)
+            .with_child(player_hud(entity, player.index));
+    }
+}
+
+fn player_hud(player: Entity, index: usize) -> impl Bundle {
+    (
+        Name::new(format!("PlayerHud({})", index + 1)),
+        Node {
+            padding: UiRect::all(Vw(1.0)),
+            row_gap: Vw(0.6),
+            ..Node::COLUMN_LEFT
+        },
+        BorderRadius::all(Vw(1.0)),
+        ThemeColor::Popup.set::<BackgroundColor>(),
+        Pickable::IGNORE,
+        PlayerHud(player),
+        children![
+            widget::small_label(format!("[b]Player {}", index + 1)),
+            hud_meter(player, HudStat::Health, ThemeColor::Health),
+            hud_meter(player, HudStat::Shield, ThemeColor::Shield),
+            hud_meter(player, HudStat::Experience, ThemeColor::Experience),
+            hud_meter(player, HudStat::Dash, ThemeColor::Cooldown),
+            hud_meter(player, HudStat::Shot, ThemeColor::Cooldown),
+            (widget::small_label(""), HudCurrency(player)),
+            (
+                Name::new("StatusIcons"),
+                Node {
+                    column_gap: Vw(0.6),
+                    ..Node::ROW
+                },
+                HudStatus {
+                    player,
+                    shown: vec![],
+                },
+            ),
+        ],
+    )
+}
+
+/// The HUD panel for the given player.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PlayerHud(Entity);
+
+impl Configure for PlayerHud {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+#[derive(Reflect, Copy, Clone, Eq, PartialEq, Debug)]
+enum HudStat {
+    Health,
+    Shield,
+    Experience,
+    Dash,
+    Shot,
+}
+
+fn hud_meter(player: Entity, stat: HudStat, color: ThemeColor) -> impl Bundle {
+    (
+        Name::new(format!("HudMeter({stat:?})")),
+        Node {
+            column_gap: Vw(1.0),
+            ..Node::ROW_MID
+        },
+        HudMeter { player, stat },
+        children![
+            widget::meter(Vw(20.0), Vw(1.6), color),
+            widget::small_label(""),
+        ],
+    )
+}
+
+/// A [`widget::meter`] and label showing one of a player's stats.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HudMeter {
+    player: Entity,
+    stat: HudStat,
+}
+
+impl Configure for HudMeter {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_hud_meters(
+    accessibility_settings: Res<AccessibilitySettings>,
+    player_query: Query<(
+        Ref<Health>,
+        Option<Ref<Shield>>,
+        Option<Ref<Experience>>,
+        Option<Ref<Dash>>,
+        Option<Ref<ShotCooldown>>,
+        Has<Downed>,
+    )>,
+    mut hud_query: Query<(Ref<HudMeter>, &Children, &mut Node)>,
+    mut meter_query: Query<&mut Meter>,
+    mut text_query: Query<&mut RichText>,
+) {
+    for (hud, children, mut node) in &mut hud_query {
+        let (health, shield, experience, dash, shot, downed) = c!(player_query.get(hud.player));
+        let changed = match hud.stat {
+            HudStat::Health => health.is_changed(),
+            HudStat::Shield => shield.as_ref().is_some_and(Ref::is_changed),
+            HudStat::Experience => experience.as_ref().is_some_and(Ref::is_changed),
+            HudStat::Dash => dash.as_ref().is_some_and(Ref::is_changed),
+            HudStat::Shot => {
+                accessibility_settings.is_changed() || shot.as_ref().is_some_and(Ref::is_changed)
+            },
+        };
+        cq!(hud.is_added() || changed);
+
+        let (fill, text) = match hud.stat {
+            HudStat::Health if downed => (health.current / health.max, "[b]Downed".to_string()),
+            HudStat::Health => (
+                health.current / health.max,
+                format!("{:.0}/{:.0}", health.current.ceil(), health.max),
+            ),
+            // Hide the shield meter if there's no shield.
+            HudStat::Shield => match shield.as_ref().filter(|shield| shield.max > 0.0) {
+                Some(shield) => (
+                    shield.current / shield.max,
+                    format!("{:.0}/{:.0}", shield.current.ceil(), shield.max),
+                ),
+                None => (0.0, String::new()),
+            },
+            HudStat::Experience => match &experience {
+                Some(experience) => (
+                    experience.current as f32 / experience.next_level() as f32,
+                    format!(
+                        "Lv {} {}/{}",
+                        experience.level,
+                        experience.current,
+                        experience.next_level(),
+                    ),
+                ),
+                None => (0.0, String::new()),
+            },
+            HudStat::Dash => match &dash {
+                Some(dash) => cooldown_meter("Dash", dash.remaining, dash.cooldown),
+                None => (0.0, String::new()),
+            },
+            // The shot cooldown only applies with auto-fire.
+            HudStat::Shot => match shot.as_ref().filter(|_| accessibility_settings.auto_fire) {
+                Some(shot) => cooldown_meter("Shot", shot.remaining, shot.interval),
+                None => (0.0, String::new()),
+            },
+        };
+
+        // Hide meters for stats the player doesn't have.
+        let display = if text.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        if node.display != display {
+            node.display = display;
+        }
+        cq!(!text.is_empty());
+
+        let meter = *c!(children.first());
+        c!(meter_query.get_mut(meter)).set_if_neq(Meter(fill));
+
+        let label = *c!(children.get(1));
+        c!(text_query.get_mut(label)).sections = parse_rich(text);
+    }
+}
+
+/// The meter fill and label for a cooldown, filling up as it recharges.
+fn cooldown_meter(name: &str, remaining: f32, duration: f32) -> (f32, String) {
+    if remaining > 0.0 && duration > 0.0 {
+        (
+            1.0 - remaining / duration,
+            format!("{name} {remaining:.1}s"),
+        )
+    } else {
+        (1.0, format!("{name} [b]Ready"))
+    }
+}
+
+/// A label showing a player's currency.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HudCurrency(Entity);
+
+impl Configure for HudCurrency {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_hud_currency(
+    wallet_query: Query<Ref<Wallet>>,
+    mut hud_query: Query<(Ref<HudCurrency>, &mut RichText)>,
+) {
+    for (hud, mut text) in &mut hud_query {
+        let wallet = c!(wallet_query.get(hud.0));
+        cq!(hud.is_added() || wallet.is_changed());
+        text.sections = parse_rich(format!("Coins: [b]{}", wallet.0));
+    }
+}
+
+/// A row of icons for a player's active status effects.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct HudStatus {
+    player: Entity,
+    /// The text of each icon currently shown.
+    shown: Vec<String>,
+}
+
+impl Configure for HudStatus {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_hud_status(
+    mut commands: Commands,
+    player_query: Query<Option<&ActiveDOT>>,
+    mut hud_query: Query<(Entity, &mut HudStatus, Option<&Children>)>,
+    mut text_query: Query<&mut RichText>,
+) {
+    for (entity, mut hud, children) in &mut hud_query {
+        let dot = c!(player_query.get(hud.player));
+        let icons = dot
+            .into_iter()
+            .map(|dot| {
+                let name = match dot.kind {
+                    DamageKind::Physical => "Damage",
+                    DamageKind::Fire => "Burn",
+                    DamageKind::Poison => "Poison",
+                    DamageKind::Explosive => "Blast",
+                };
+                format!("[b]{name}[r] {:.1}s", dot.remaining.max(0.0))
+            })
+            .collect::<Vec<_>>();
+        cq!(hud.shown != icons);
+
+        // Reuse existing icons, then spawn or despawn the difference.
+        let children = children.map(|x| x.to_vec()).unwrap_or_default();
+        for (i, text) in icons.iter().enumerate() {
+            if let Some(&icon) = children.get(i) {
+                c!(text_query.get_mut(icon)).sections = parse_rich(text);
+            } else {
+                commands.entity(entity).with_child(status_icon(text));
+            }
+        }
+        for &icon in children.iter().skip(icons.len()) {
+            commands.entity(icon).despawn();
+        }
+        hud.shown = icons;
+    }
+}
+
+fn status_icon(text: &str) -> impl Bundle {
+    (
+        widget::small_label(text),
+        Node {
+            padding: UiRect::axes(Vw(0.8), Vw(0.3)),
+            ..default()
+        },
+        BorderRadius::all(Vw(0.5)),
+        ThemeColor::Body.set::<BackgroundColor>(),
+    )
+}
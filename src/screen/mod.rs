@@ -1,13 +1,19 @@
 pub mod fade;
 pub mod gameplay;
 mod loading;
+mod music;
 mod splash;
 mod title;
 
 use bevy::ecs::schedule::ScheduleConfigs;
 use bevy::ecs::system::ScheduleSystem;
 
+use crate::core::camera::CameraBounds;
+use crate::core::camera::CameraGroup;
+use crate::core::camera::CameraLookAhead;
 use crate::core::camera::CameraRoot;
+use crate::core::camera::CameraShake;
+use crate::core::camera::CameraZoom;
 use crate::core::window::WindowReady;
 use crate::game::world::Level;
 use crate::menu::Menu;
@@ -87,13 +93,35 @@ impl Configure for Screen {
             title::plugin,
             loading::plugin,
             gameplay::plugin,
+            music::plugin,
         ));
     }
 }
 
-fn reset_screen_camera(camera_root: Res<CameraRoot>, mut camera_query: Query<&mut Transform>) {
-    let mut transform = r!(camera_query.get_mut(camera_root.primary));
+fn reset_screen_camera(
+    camera_root: Res<CameraRoot>,
+    mut camera_query: Query<(&mut Transform, &mut Projection)>,
+    mut control_query: Query<(
+        &mut CameraBounds,
+        &mut CameraLookAhead,
+        &mut CameraZoom,
+        &mut CameraGroup,
+        &mut CameraShake,
+    )>,
+) {
+    let (mut transform, mut projection) = r!(camera_query.get_mut(camera_root.primary));
     *transform = default();
+    if let Projection::Orthographic(projection) = projection.as_mut() {
+        projection.scale = 1.0;
+    }
+
+    let (mut bounds, mut look_ahead, mut zoom, mut group, mut shake) =
+        r!(control_query.get_mut(camera_root.primary));
+    *bounds = default();
+    *look_ahead = default();
+    *zoom = default();
+    *group = default();
+    shake.reset();
 }
 
 /// The total time elapsed in the current screen.
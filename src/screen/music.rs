@@ -0,0 +1,161 @@
+use crate::core::audio::AudioFade;
+use crate::core::audio::AudioSettings;
+use crate::core::audio::music_audio;
+use crate::game::room::CurrentRoom;
+use crate::game::room::Room;
+use crate::game::room::RoomKind;
+use crate::game::world::Level;
+use crate::prelude::*;
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(MusicAssets, MusicDirector)>();
+}
+
+#[derive(AssetCollection, Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct MusicAssets {
+    #[asset(path = "audio/music/Marimba_Duet.ogg")]
+    title: Handle<AudioSource>,
+    #[asset(path = "audio/music/summer.ogg")]
+    summer: Handle<AudioSource>,
+    #[asset(path = "audio/music/Dust and Spurs.ogg")]
+    dust_and_spurs: Handle<AudioSource>,
+    #[asset(path = "audio/music/240376__edtijo__happy-8bit-pixel-adenture.ogg")]
+    pixel_adventure: Handle<AudioSource>,
+    #[asset(path = "audio/music/Buckin Bacteria.ogg")]
+    buckin_bacteria: Handle<AudioSource>,
+    #[asset(path = "audio/music/545458__bertsz__bit-forest-evil-theme-music.ogg")]
+    forest_evil: Handle<AudioSource>,
+}
+
+impl Configure for MusicAssets {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_collection::<Self>();
+    }
+}
+
+impl MusicAssets {
+    fn get(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::Title => &self.title,
+            MusicTrack::Level(1) => &self.summer,
+            MusicTrack::Level(2) => &self.dust_and_spurs,
+            MusicTrack::Level(_) => &self.pixel_adventure,
+            MusicTrack::Shop => &self.buckin_bacteria,
+            MusicTrack::Boss => &self.forest_evil,
+        }
+        .clone()
+    }
+}
+
+#[derive(Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MusicTrack {
+    Title,
+    Level(usize),
+    Shop,
+    Boss,
+}
+
+/// The seconds it takes to crossfade between tracks.
+const CROSSFADE_SECS: f32 = 1.5;
+
+/// The music volume multiplier while the game is paused.
+const PAUSE_DUCK: f32 = 0.35;
+
+/// Picks the [`MusicTrack`] for the current screen, level and room, and crossfades to it.
+///
+/// The track keeps playing across screen changes as long as it doesn't change.
+#[derive(Resource, Reflect, Default, PartialEq, Debug)]
+#[reflect(Resource)]
+pub struct MusicDirector {
+    pub track: Option<MusicTrack>,
+    playing: Option<Entity>,
+}
+
+impl Configure for MusicDirector {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            (choose_music_track, play_music_track)
+                .chain()
+                .in_set(UpdateSystems::SyncLate),
+        );
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn choose_music_track(
+    screen: CurrentRef<Screen>,
+    level: CurrentRef<Level>,
+    current_room: Res<CurrentRoom>,
+    room_query: Query<&Room>,
+    mut director: ResMut<MusicDirector>,
+) {
+    let track = match screen.get() {
+        Some(Screen::Title) => Some(MusicTrack::Title),
+        // Keep playing the previous track while loading.
+        Some(Screen::Loading) => return,
+        Some(Screen::Gameplay) => {
+            let room_kind = current_room
+                .0
+                .and_then(|entity| room_query.get(entity).ok())
+                .map(|room| room.kind);
+            match room_kind {
+                Some(RoomKind::Boss) => Some(MusicTrack::Boss),
+                Some(RoomKind::Shop) => Some(MusicTrack::Shop),
+                _ => level.get().map(|level| MusicTrack::Level(level.0)),
+            }
+        },
+        _ => None,
+    };
+    let playing = director.playing;
+    director.set_if_neq(MusicDirector { track, playing });
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn play_music_track(
+    mut commands: Commands,
+    audio_settings: Res<AudioSettings>,
+    music_assets: Option<Res<MusicAssets>>,
+    pause: CurrentRef<Pause>,
+    screen: CurrentRef<Screen>,
+    mut director: ResMut<MusicDirector>,
+    mut fade_query: Query<&mut AudioFade>,
+) {
+    if director.is_changed() {
+        if let Some(mut fade) = director
+            .playing
+            .and_then(|entity| fade_query.get_mut(entity).ok())
+        {
+            fade.fade_out();
+        }
+        let track = director.track;
+        // Avoid triggering change detection again.
+        director.bypass_change_detection().playing = track.and_then(|track| {
+            let handle = music_assets.as_ref()?.get(track);
+            Some(
+                commands
+                    .spawn(music_audio(
+                        &audio_settings,
+                        handle,
+                        AudioFade::fade_in(CROSSFADE_SECS),
+                    ))
+                    .id(),
+            )
+        });
+    }
+
+    // Duck the music under the pause menu.
+    let mut fade = rq!(director
+        .playing
+        .and_then(|entity| fade_query.get_mut(entity).ok()));
+    fade.target = if pause.is_enabled() && screen.is_in(&Screen::Gameplay) {
+        PAUSE_DUCK
+    } else {
+        1.0
+    };
+}
@@ -0,0 +1,257 @@
+//! Keyboard and gamepad navigation between UI elements.
+
+use std::time::Duration;
+
+use bevy::picking::backend::HitData;
+use bevy::picking::pointer::Location;
+use bevy::picking::pointer::PointerButton;
+use bevy::picking::pointer::PointerId;
+use bevy::render::camera::NormalizedRenderTarget;
+use bevy::window::WindowRef;
+
+use crate::core::audio::AudioSettings;
+use crate::core::audio::ui_audio;
+use crate::core::camera::CameraRoot;
+use crate::core::window::WindowRoot;
+use crate::prelude::*;
+use crate::theme::ThemeAssets;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Focus, Focusable, FocusAction)>();
+}
+
+/// The UI element that keyboard and gamepad input acts on.
+///
+/// A focused element is themed as hovered by [`InteractionTheme`].
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct Focus(pub Option<Entity>);
+
+impl Configure for Focus {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(Update, clear_lost_focus.in_set(UpdateSystems::SyncEarly));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn clear_lost_focus(mut focus: ResMut<Focus>, focusable_query: Query<(), With<Focusable>>) {
+    let target = rq!(focus.0);
+    rq!(!focusable_query.contains(target));
+    focus.0 = None;
+}
+
+/// A UI element that can receive [`Focus`].
+#[derive(Component, Reflect, Copy, Clone, Default, Eq, PartialEq, Debug)]
+#[reflect(Component)]
+pub enum Focusable {
+    /// Activated by a click.
+    #[default]
+    Button,
+    /// Adjusted by a click on its first or last child, like a [`widget::selector`].
+    Selector,
+}
+
+impl Configure for Focusable {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_observer(focus_on_hover);
+    }
+}
+
+fn focus_on_hover(
+    trigger: Trigger<Pointer<Over>>,
+    focusable_query: Query<Option<&InteractionDisabled>, With<Focusable>>,
+    mut focus: ResMut<Focus>,
+) {
+    let target = r!(trigger.get_target());
+    let disabled = rq!(focusable_query.get(target));
+    rq!(!matches!(disabled, Some(InteractionDisabled(true))));
+
+    focus.0 = Some(target);
+}
+
+#[derive(Actionlike, Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FocusAction {
+    #[actionlike(DualAxis)]
+    Navigate,
+    Activate,
+}
+
+impl Configure for FocusAction {
+    fn configure(app: &mut App) {
+        app.init_resource::<ActionState<Self>>();
+        app.insert_resource(
+            InputMap::default()
+                .with_dual_axis(Self::Navigate, GamepadStick::LEFT)
+                .with_dual_axis(Self::Navigate, VirtualDPad::dpad())
+                .with_dual_axis(Self::Navigate, VirtualDPad::arrow_keys())
+                .with(Self::Activate, GamepadButton::South)
+                .with(Self::Activate, KeyCode::Enter)
+                .with(Self::Activate, KeyCode::NumpadEnter),
+        );
+        app.add_plugins(InputManagerPlugin::<Self>::default());
+        app.add_systems(
+            Update,
+            (
+                navigate_focus,
+                activate_focus.run_if(action_just_pressed(Self::Activate)),
+            )
+                .chain()
+                .in_set(UpdateSystems::RecordInput),
+        );
+    }
+}
+
+/// Seconds to hold a direction before navigation starts repeating.
+const NAVIGATE_REPEAT_DELAY: f32 = 0.4;
+/// Seconds between repeats while a direction is held.
+const NAVIGATE_REPEAT_INTERVAL: f32 = 0.12;
+
+#[derive(Default)]
+struct NavigateRepeat {
+    direction: IVec2,
+    timer: f32,
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn navigate_focus(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    action: Res<ActionState<FocusAction>>,
+    mut repeat: Local<NavigateRepeat>,
+    mut focus: ResMut<Focus>,
+    focusable_query: Query<(
+        Entity,
+        &Focusable,
+        &ComputedNode,
+        &GlobalTransform,
+        &InheritedVisibility,
+        Option<&InteractionDisabled>,
+    )>,
+    children_query: Query<&Children>,
+    disabled_query: Query<&InteractionDisabled>,
+    window_root: Res<WindowRoot>,
+    camera_root: Res<CameraRoot>,
+    audio_settings: Res<AudioSettings>,
+    assets: Res<ThemeAssets>,
+) {
+    // Snap the input to a cardinal direction.
+    let input = action.axis_pair(&FocusAction::Navigate);
+    let direction = if input.length() < 0.5 {
+        IVec2::ZERO
+    } else if input.x.abs() > input.y.abs() {
+        IVec2::new(input.x.signum() as i32, 0)
+    } else {
+        IVec2::new(0, input.y.signum() as i32)
+    };
+
+    // Navigate once on press, then repeatedly while held.
+    if direction != repeat.direction {
+        repeat.direction = direction;
+        repeat.timer = NAVIGATE_REPEAT_DELAY;
+        rq!(direction != IVec2::ZERO);
+    } else {
+        rq!(direction != IVec2::ZERO);
+        repeat.timer -= time.delta_secs();
+        rq!(repeat.timer <= 0.0);
+        repeat.timer = NAVIGATE_REPEAT_INTERVAL;
+    }
+
+    // Collect the elements that can currently be focused, in UI coordinates (+Y is down).
+    let candidates = focusable_query
+        .iter()
+        .filter(|(_, _, node, _, visibility, disabled)| {
+            visibility.get()
+                && !node.is_empty()
+                && !matches!(disabled, Some(InteractionDisabled(true)))
+        })
+        .map(|(entity, &focusable, _, gt, ..)| (entity, focusable, gt.translation().xy()))
+        .collect::<Vec<_>>();
+    let current = focus
+        .0
+        .and_then(|target| candidates.iter().find(|(entity, ..)| *entity == target));
+
+    // Adjust a focused selector left or right.
+    if let (Some(&(selector, Focusable::Selector, _)), 0) = (current, direction.y) {
+        let children = r!(children_query.get(selector));
+        let button = *r!(if direction.x < 0 {
+            children.first()
+        } else {
+            children.last()
+        });
+        rq!(!matches!(
+            disabled_query.get(button),
+            Ok(InteractionDisabled(true))
+        ));
+        click(&mut commands, button, &window_root, &camera_root);
+        return;
+    }
+
+    let next = if let Some(&(current, _, position)) = current {
+        // Choose the nearest element in the direction, preferring elements that are aligned.
+        let direction = Vec2::new(direction.x as f32, -direction.y as f32);
+        candidates
+            .iter()
+            .filter(|(entity, ..)| *entity != current)
+            .filter_map(|&(entity, _, other)| {
+                let offset = other - position;
+                let along = offset.dot(direction);
+                let across = offset.perp_dot(direction).abs();
+                (along > 1.0).then_some((entity, along + 2.0 * across))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    } else {
+        // Start from the top-left element.
+        candidates
+            .iter()
+            .min_by(|a, b| a.2.y.total_cmp(&b.2.y).then(a.2.x.total_cmp(&b.2.x)))
+            .map(|&(entity, ..)| entity)
+    };
+
+    focus.0 = Some(rq!(next));
+    commands.spawn(ui_audio(&audio_settings, assets.sfx_hover.clone()));
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn activate_focus(
+    mut commands: Commands,
+    focus: Res<Focus>,
+    focusable_query: Query<(&Focusable, Option<&InteractionDisabled>)>,
+    window_root: Res<WindowRoot>,
+    camera_root: Res<CameraRoot>,
+) {
+    let target = rq!(focus.0);
+    let (&focusable, disabled) = r!(focusable_query.get(target));
+    rq!(focusable == Focusable::Button);
+    rq!(!matches!(disabled, Some(InteractionDisabled(true))));
+
+    click(&mut commands, target, &window_root, &camera_root);
+}
+
+/// Click a UI element as if with the mouse, so that its click observers run.
+fn click(
+    commands: &mut Commands,
+    target: Entity,
+    window_root: &WindowRoot,
+    camera_root: &CameraRoot,
+) {
+    let window = r!(WindowRef::Entity(window_root.primary).normalize(None));
+    commands.trigger_targets(
+        Pointer::new(
+            PointerId::Mouse,
+            Location {
+                target: NormalizedRenderTarget::Window(window),
+                position: Vec2::ZERO,
+            },
+            Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(camera_root.primary, 0.0, None, None),
+                duration: Duration::ZERO,
+            },
+        ),
+        target,
+    );
+}
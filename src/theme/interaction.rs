@@ -7,12 +7,14 @@ use crate::core::audio::AudioSettings;
 use crate::core::audio::ui_audio;
 use crate::prelude::*;
 use crate::theme::ThemeAssets;
+use crate::theme::focus::Focus;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure::<(
         Previous<Interaction>,
         InteractionDisabled,
         InteractionTheme<ThemeColorFor<BackgroundColor>>,
+        InteractionTheme<ThemeColorFor<BorderColor>>,
         InteractionTheme<NodeOffset>,
         TargetInteractionTheme<ThemeColorForText>,
         TargetInteractionTheme<NodeOffset>,
@@ -55,33 +57,32 @@ impl<C: Component<Mutability = Mutable> + Clone + Typed + FromReflect + GetTypeR
 
 #[cfg_attr(feature = "native_dev", hot)]
 fn apply_interaction_theme<C: Component<Mutability = Mutable> + Clone>(
-    mut interaction_query: Query<
-        (
-            Option<&InteractionDisabled>,
-            &Previous<Interaction>,
-            &Interaction,
-            &InteractionTheme<C>,
-            &mut C,
-        ),
-        Or<(
-            Changed<InteractionDisabled>,
-            Changed<Previous<Interaction>>,
-            Changed<Interaction>,
-        )>,
-    >,
+    focus: Res<Focus>,
+    mut interaction_query: Query<(
+        Entity,
+        Option<Ref<InteractionDisabled>>,
+        Ref<Previous<Interaction>>,
+        Ref<Interaction>,
+        &InteractionTheme<C>,
+        &mut C,
+    )>,
 ) {
-    for (is_disabled, previous, current, table, mut value) in &mut interaction_query {
+    for (entity, is_disabled, previous, current, table, mut value) in &mut interaction_query {
+        cq!(focus.is_changed()
+            || is_disabled.as_ref().is_some_and(Ref::is_changed)
+            || previous.is_changed()
+            || current.is_changed());
         // Add 1 frame of delay when going from pressed -> hovered.
         cq!(!matches!(
-            (previous.0, current),
+            (previous.0, *current),
             (Interaction::Pressed, Interaction::Hovered),
         ));
 
         // Clone the field corresponding to the current interaction state.
-        *value = if matches!(is_disabled, Some(InteractionDisabled(true))) {
+        *value = if matches!(is_disabled.as_deref(), Some(InteractionDisabled(true))) {
             &table.disabled
         } else {
-            match current {
+            match focused_interaction(&focus, entity, *current) {
                 Interaction::None => &table.none,
                 Interaction::Hovered => &table.hovered,
                 Interaction::Pressed => &table.pressed,
@@ -116,33 +117,31 @@ impl<C: Component<Mutability = Mutable> + Clone + Typed + FromReflect + GetTypeR
 
 #[cfg_attr(feature = "native_dev", hot)]
 fn apply_target_interaction_theme<C: Component<Mutability = Mutable> + Clone>(
+    focus: Res<Focus>,
     mut table_query: Query<(&TargetInteractionTheme<C>, &mut C)>,
-    interaction_query: Query<
-        (
-            Option<&InteractionDisabled>,
-            &Previous<Interaction>,
-            &Interaction,
-        ),
-        Or<(
-            Changed<InteractionDisabled>,
-            Changed<Previous<Interaction>>,
-            Changed<Interaction>,
-        )>,
-    >,
+    interaction_query: Query<(
+        Option<Ref<InteractionDisabled>>,
+        Ref<Previous<Interaction>>,
+        Ref<Interaction>,
+    )>,
 ) {
     for (table, mut value) in &mut table_query {
         let (is_disabled, previous, current) = cq!(interaction_query.get(table.target));
+        cq!(focus.is_changed()
+            || is_disabled.as_ref().is_some_and(Ref::is_changed)
+            || previous.is_changed()
+            || current.is_changed());
         // Add 1 frame of delay when going from pressed -> hovered.
         cq!(!matches!(
-            (previous.0, current),
+            (previous.0, *current),
             (Interaction::Pressed, Interaction::Hovered),
         ));
 
         // Clone the field corresponding to the current interaction state.
-        *value = if matches!(is_disabled, Some(InteractionDisabled(true))) {
+        *value = if matches!(is_disabled.as_deref(), Some(InteractionDisabled(true))) {
             &table.disabled
         } else {
-            match current {
+            match focused_interaction(&focus, table.target, *current) {
                 Interaction::None => &table.none,
                 Interaction::Hovered => &table.hovered,
                 Interaction::Pressed => &table.pressed,
@@ -152,6 +151,15 @@ fn apply_target_interaction_theme<C: Component<Mutability = Mutable> + Clone>(
     }
 }
 
+/// Treat the focused entity as hovered.
+fn focused_interaction(focus: &Focus, entity: Entity, interaction: Interaction) -> Interaction {
+    if interaction == Interaction::None && focus.0 == Some(entity) {
+        Interaction::Hovered
+    } else {
+        interaction
+    }
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 #[require(Previous<Interaction>)]
@@ -3,8 +3,10 @@
 #![allow(dead_code)]
 
 pub mod color;
+pub mod focus;
 pub mod grid;
 pub mod interaction;
+pub mod setting;
 pub mod text;
 pub mod tooltip;
 pub mod widget;
@@ -14,10 +16,12 @@ pub mod prelude {
     pub use super::color::ThemeColor;
     pub use super::color::ThemeColorFor;
     pub use super::color::ThemeColorForText;
+    pub use super::focus::Focusable;
     pub use super::grid::GridAlignment;
     pub use super::interaction::InteractionDisabled;
     pub use super::interaction::InteractionSfx;
     pub use super::interaction::InteractionTheme;
+    pub use super::setting::SettingBinding;
     pub use super::text::BOLD_FONT_HANDLE;
     pub use super::text::DynamicFontSize;
     pub use super::text::FONT_HANDLE;
@@ -30,10 +34,11 @@ pub mod prelude {
 use crate::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<ThemeAssets>();
+    app.configure::<(ThemeAssets, widget::Meter)>();
 
     app.add_plugins((
         color::plugin,
+        focus::plugin,
         grid::plugin,
         interaction::plugin,
         text::plugin,
@@ -0,0 +1,190 @@
+//! Selector widgets bound to reflected fields on settings resources.
+
+use bevy::reflect::DynamicEnum;
+use bevy::reflect::DynamicVariant;
+use bevy::reflect::ReflectRef;
+use bevy::reflect::TypeInfo;
+
+use crate::prelude::*;
+
+/// Binds a [`widget::setting_selector`] to a reflected field on the resource `R`.
+///
+/// The path is a [reflect path](bevy::reflect::GetPath) relative to `R`, e.g. `"music_volume"`.
+#[derive(Component)]
+pub struct SettingBinding<R: Resource + Reflect> {
+    pub path: &'static str,
+    pub kind: SettingKind,
+    _phantom: PhantomData<R>,
+}
+
+impl<R: Resource + Reflect> Configure for SettingBinding<R> {
+    fn configure(app: &mut App) {
+        app.add_systems(
+            Update,
+            update_setting_selectors::<R>.in_set(UpdateSystems::Update),
+        );
+    }
+}
+
+/// How a [`SettingBinding`] displays and adjusts its field.
+#[derive(Copy, Clone, Debug)]
+pub enum SettingKind {
+    /// An `f32` between `min` and `max`, adjusted by `step`.
+    Range {
+        min: f32,
+        max: f32,
+        step: f32,
+        format: fn(f32) -> String,
+    },
+    /// A `bool`, adjusted left to `false` and right to `true`.
+    Toggle { off: &'static str, on: &'static str },
+    /// A fieldless enum, cycled through its variants in declaration order.
+    Cycle { format: fn(&str) -> String },
+}
+
+impl<R: Resource + Reflect> SettingBinding<R> {
+    pub fn new(path: &'static str, kind: SettingKind) -> Self {
+        Self {
+            path,
+            kind,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn range(
+        path: &'static str,
+        min: f32,
+        max: f32,
+        step: f32,
+        format: fn(f32) -> String,
+    ) -> Self {
+        Self::new(
+            path,
+            SettingKind::Range {
+                min,
+                max,
+                step,
+                format,
+            },
+        )
+    }
+
+    /// A range from 0% to 100% in steps of 10%.
+    pub fn percent(path: &'static str) -> Self {
+        Self::range(path, 0.0, 1.0, 0.1, |x| format!("{:.0}%", x * 100.0))
+    }
+
+    pub fn toggle(path: &'static str, off: &'static str, on: &'static str) -> Self {
+        Self::new(path, SettingKind::Toggle { off, on })
+    }
+
+    pub fn cycle(path: &'static str, format: fn(&str) -> String) -> Self {
+        Self::new(path, SettingKind::Cycle { format })
+    }
+
+    /// The text to display for the current value.
+    fn display(&self, resource: &R) -> Option<String> {
+        let field = resource.reflect_path(self.path).ok()?;
+        Some(match self.kind {
+            SettingKind::Range { format, .. } => format(*field.try_downcast_ref::<f32>()?),
+            SettingKind::Toggle { off, on } => {
+                let value = *field.try_downcast_ref::<bool>()?;
+                (if value { on } else { off }).to_string()
+            },
+            SettingKind::Cycle { format } => {
+                let ReflectRef::Enum(value) = field.reflect_ref() else {
+                    return None;
+                };
+                format(value.variant_name())
+            },
+        })
+    }
+
+    /// Whether the value can be adjusted (left, right).
+    fn can_step(&self, resource: &R) -> Option<(bool, bool)> {
+        let field = resource.reflect_path(self.path).ok()?;
+        Some(match self.kind {
+            SettingKind::Range { min, max, .. } => {
+                let value = *field.try_downcast_ref::<f32>()?;
+                (value > min + f32::EPSILON, value < max - f32::EPSILON)
+            },
+            SettingKind::Toggle { .. } => {
+                let value = *field.try_downcast_ref::<bool>()?;
+                (value, !value)
+            },
+            SettingKind::Cycle { .. } => (true, true),
+        })
+    }
+
+    /// Adjust the value left or right.
+    pub fn step(&self, resource: &mut R, right: bool) {
+        let field = r!(resource.reflect_path_mut(self.path));
+        match self.kind {
+            SettingKind::Range { min, max, step, .. } => {
+                let value = r!(field.try_downcast_mut::<f32>());
+                let step = if right { step } else { -step };
+                *value = (*value + step).clamp(min, max);
+            },
+            SettingKind::Toggle { .. } => {
+                *r!(field.try_downcast_mut::<bool>()) = right;
+            },
+            SettingKind::Cycle { .. } => {
+                let ReflectRef::Enum(value) = field.reflect_ref() else {
+                    return;
+                };
+                let TypeInfo::Enum(info) = r!(field.get_represented_type_info()) else {
+                    return;
+                };
+                let len = info.variant_len();
+                let index = if right {
+                    (value.variant_index() + 1) % len
+                } else {
+                    (value.variant_index() + len - 1) % len
+                };
+                let name = r!(info.variant_at(index)).name();
+                field.apply(&DynamicEnum::new(name, DynamicVariant::Unit));
+            },
+        }
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_setting_selectors<R: Resource + Reflect>(
+    resource: Res<R>,
+    selector_query: Query<(Entity, Ref<SettingBinding<R>>)>,
+    children_query: Query<&Children>,
+    mut text_query: Query<&mut RichText>,
+    mut disabled_query: Query<&mut InteractionDisabled>,
+) {
+    for (entity, binding) in &selector_query {
+        cq!(resource.is_changed() || binding.is_added());
+        let (can_left, can_right) = c!(binding.can_step(&resource));
+        let children = c!(children_query.get(entity))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let left = **c!(children.first());
+        c!(disabled_query.get_mut(left)).0 = !can_left;
+
+        let mid = **c!(children.get(1));
+        let mid_children = c!(children_query.get(mid));
+        let label = *c!(mid_children.first());
+        c!(text_query.get_mut(label)).sections = parse_rich(c!(binding.display(&resource)));
+
+        let right = **c!(children.get(2));
+        c!(disabled_query.get_mut(right)).0 = !can_right;
+    }
+}
+
+/// Adjust the [`SettingBinding`] of a selector button's parent.
+pub(super) fn step_setting<R: Resource + Reflect, const RIGHT: bool>(
+    trigger: Trigger<Pointer<Click>>,
+    child_of_query: Query<&ChildOf>,
+    binding_query: Query<&SettingBinding<R>>,
+    mut resource: ResMut<R>,
+) {
+    let target = r!(trigger.get_target());
+    let selector = r!(child_of_query.get(target)).parent();
+    let binding = r!(binding_query.get(selector));
+    binding.step(&mut resource, RIGHT);
+}
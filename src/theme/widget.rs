@@ -3,6 +3,7 @@ use bevy::ecs::system::IntoObserverSystem;
 use crate::animation::backup::Backup;
 use crate::animation::offset::NodeOffset;
 use crate::prelude::*;
+use crate::theme::setting::step_setting;
 
 pub fn overlay(z: i32) -> impl Bundle {
     (
@@ -97,6 +98,10 @@ pub fn label(text: impl AsRef<str>) -> impl Bundle {
     label_base(Vw(3.5), ThemeColor::BodyText, text)
 }
 
+pub fn small_label(text: impl AsRef<str>) -> impl Bundle {
+    label_base(Vw(2.0), ThemeColor::BodyText, text)
+}
+
 pub fn paragraph(text: &'static str) -> impl Bundle {
     (
         Name::new("Paragraph"),
@@ -125,7 +130,22 @@ where
     B: Bundle,
     I: Sync + IntoObserverSystem<E, B, M>,
 {
-    button_base(Vw(3.0), Vw(4.0), Vw(3.0), text, action)
+    (
+        button_base(Vw(3.0), Vw(4.0), Vw(3.0), text, action),
+        Focusable::Button,
+    )
+}
+
+pub fn compact_button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: Sync + IntoObserverSystem<E, B, M>,
+{
+    (
+        button_base(Vw(16.0), Vw(4.0), Vw(2.5), text, action),
+        Focusable::Button,
+    )
 }
 
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
@@ -134,7 +154,10 @@ where
     B: Bundle,
     I: Sync + IntoObserverSystem<E, B, M>,
 {
-    button_base(Vw(30.0), Vw(7.0), Vw(3.0), text, action)
+    (
+        button_base(Vw(30.0), Vw(7.0), Vw(3.0), text, action),
+        Focusable::Button,
+    )
 }
 
 pub fn wide_button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
@@ -143,7 +166,10 @@ where
     B: Bundle,
     I: Sync + IntoObserverSystem<E, B, M>,
 {
-    button_base(Vw(38.0), Vw(7.0), Vw(3.0), text, action)
+    (
+        button_base(Vw(38.0), Vw(7.0), Vw(3.0), text, action),
+        Focusable::Button,
+    )
 }
 
 pub fn big_button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
@@ -152,7 +178,20 @@ where
     B: Bundle,
     I: Sync + IntoObserverSystem<E, B, M>,
 {
-    button_base(Vw(38.0), Vw(10.0), Vw(4.0), text, action)
+    (
+        button_base(Vw(38.0), Vw(10.0), Vw(4.0), text, action),
+        Focusable::Button,
+    )
+}
+
+/// A [`small_button`] that can't be focused, for use inside a [`selector`].
+fn arrow_button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: Sync + IntoObserverSystem<E, B, M>,
+{
+    button_base(Vw(3.0), Vw(4.0), Vw(3.0), text, action)
 }
 
 fn button_base<E, B, M, I>(
@@ -227,17 +266,78 @@ where
         Name::new("Selector"),
         Node {
             width: Vw(35.0),
+            border: UiRect::all(Vw(0.4)),
             ..Node::ROW
         },
+        BorderRadius::MAX,
+        ThemeColor::Invisible.set::<BorderColor>(),
+        InteractionTheme {
+            none: ThemeColor::Invisible.set::<BorderColor>(),
+            hovered: ThemeColor::PrimaryHovered.set::<BorderColor>(),
+            pressed: ThemeColor::PrimaryHovered.set::<BorderColor>(),
+            disabled: ThemeColor::Invisible.set::<BorderColor>(),
+        },
+        // The arrow buttons are adjusted left and right instead of focused individually.
+        Focusable::Selector,
         marker,
         children![
-            (small_button("<", left_action), InteractionDisabled(false)),
+            (arrow_button("<", left_action), InteractionDisabled(false)),
             stretch(children![label("")]),
-            (small_button(">", right_action), InteractionDisabled(false)),
+            (arrow_button(">", right_action), InteractionDisabled(false)),
         ],
     )
 }
 
+/// A [`selector`] that adjusts the field bound by a [`SettingBinding`].
+pub fn setting_selector<R: Resource + Reflect>(binding: SettingBinding<R>) -> impl Bundle {
+    selector(binding, step_setting::<R, false>, step_setting::<R, true>)
+}
+
+/// A bar filled to the fraction in its [`Meter`], like a health bar.
+pub fn meter(width: Val, height: Val, fill: ThemeColor) -> impl Bundle {
+    (
+        Name::new("Meter"),
+        Node {
+            width,
+            height,
+            padding: UiRect::all(Vw(0.3)),
+            ..default()
+        },
+        BorderRadius::all(Vw(0.5)),
+        ThemeColor::Body.set::<BackgroundColor>(),
+        Meter(1.0),
+        children![(
+            Name::new("MeterFill"),
+            Node::DEFAULT.full_size(),
+            BorderRadius::all(Vw(0.3)),
+            fill.set::<BackgroundColor>(),
+        )],
+    )
+}
+
+/// The filled fraction of a [`meter`], from 0 to 1.
+#[derive(Component, Reflect, Copy, Clone, PartialEq, Debug)]
+#[reflect(Component)]
+pub struct Meter(pub f32);
+
+impl Configure for Meter {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, update_meter_fill.in_set(UpdateSystems::SyncLate));
+    }
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+fn update_meter_fill(
+    meter_query: Query<(&Meter, &Children), Changed<Meter>>,
+    mut node_query: Query<&mut Node>,
+) {
+    for (meter, children) in &meter_query {
+        let fill = *c!(children.first());
+        c!(node_query.get_mut(fill)).width = Percent(100.0 * meter.0.clamp(0.0, 1.0));
+    }
+}
+
 pub fn loading_bar<S: State + Clone + PartialEq + Eq + Hash + Debug>() -> impl Bundle {
     (
         Name::new("LoadingBar"),
@@ -6,6 +6,7 @@ pub mod config;
 pub mod extend;
 pub mod late_commands;
 pub mod patch;
+pub mod prefs;
 pub mod previous;
 pub mod selection;
 
@@ -18,6 +19,7 @@ pub mod prelude {
     pub use super::extend::prelude::*;
     pub use super::late_commands::LateCommands;
     pub use super::patch::Patch;
+    pub use super::prefs::Prefs;
     pub use super::previous::Previous;
     pub use super::selection::Selection;
 }
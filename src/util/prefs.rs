@@ -0,0 +1,249 @@
+use std::any::type_name;
+
+use crate::prelude::*;
+
+/// A set of settings resources that is loaded on startup and saved whenever one of them changes.
+///
+/// Settings are stored as RON in the local config directory on native, and in local storage on web.
+pub trait Prefs: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static {
+    const FILE: &'static str;
+    /// The schema version. Increment this when a change needs [`Prefs::migrate`].
+    ///
+    /// Additive changes don't need a new version if the settings use `#[serde(default)]`.
+    const VERSION: u32;
+
+    /// Upgrade settings that were saved with an older schema version.
+    fn migrate(&mut self, version: u32) {
+        let _ = version;
+    }
+
+    /// Read the settings from their resources.
+    fn gather(world: &World) -> Self;
+
+    /// Write the settings to their resources.
+    fn apply(self, world: &mut World);
+
+    /// Whether any of the settings resources changed since the last save.
+    fn is_changed(world: &World) -> bool;
+}
+
+#[derive(Serialize, Deserialize)]
+struct PrefsFile<P> {
+    version: u32,
+    settings: P,
+}
+
+/// The schema version of a [`PrefsFile`], ignoring the settings.
+#[derive(Deserialize)]
+struct PrefsVersion {
+    /// RON would require `Some(..)` for a plain `Option` field.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    version: Option<u32>,
+}
+
+fn deserialize_some<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u32>, D::Error> {
+    u32::deserialize(deserializer).map(Some)
+}
+
+fn parse_prefs<P: Prefs>(text: &str) -> Result<P, ron::error::SpannedError> {
+    let (version, mut prefs) = match ron::from_str::<PrefsVersion>(text)?.version {
+        Some(_) => {
+            let file = ron::from_str::<PrefsFile<P>>(text)?;
+            (file.version, file.settings)
+        },
+        // Settings saved before schema versioning contain the settings directly.
+        None => (0, ron::from_str::<P>(text)?),
+    };
+
+    if version < P::VERSION {
+        info!(
+            "Migrating {} from version {version} to {}",
+            type_name::<P>(),
+            P::VERSION,
+        );
+        prefs.migrate(version);
+    } else if version > P::VERSION {
+        warn!(
+            "Loading {} from a newer version ({version} > {})",
+            type_name::<P>(),
+            P::VERSION,
+        );
+    }
+
+    Ok(prefs)
+}
+
+#[cfg_attr(feature = "native_dev", hot)]
+pub fn load_prefs<P: Prefs>(world: &mut World) {
+    // Keep the defaults if nothing has been saved yet.
+    let text = rq!(storage::read(P::FILE));
+    match parse_prefs::<P>(&text) {
+        Ok(prefs) => prefs.apply(world),
+        Err(e) => {
+            error!(
+                "Failed to load {}, so changes won't be saved: {e}",
+                type_name::<P>(),
+            );
+            world.insert_resource(PrefsReadOnly::<P>(PhantomData));
+        },
+    }
+}
+
+/// Stops [`save_prefs`] from overwriting settings that failed to load.
+#[derive(Resource)]
+struct PrefsReadOnly<P: Prefs>(PhantomData<P>);
+
+#[cfg_attr(feature = "native_dev", hot)]
+pub fn save_prefs<P: Prefs>(world: &mut World) {
+    rq!(!world.contains_resource::<PrefsReadOnly<P>>());
+    rq!(P::is_changed(world));
+
+    let file = PrefsFile {
+        version: P::VERSION,
+        settings: P::gather(world),
+    };
+    let text = match ron::ser::to_string_pretty(&file, default()) {
+        Ok(text) => text,
+        Err(e) => {
+            warn!("Failed to serialize {}: {e}", type_name::<P>());
+            return;
+        },
+    };
+    if let Err(e) = storage::write(P::FILE, &text) {
+        warn!("Failed to save {}: {e}", type_name::<P>());
+    }
+}
+
+#[cfg(feature = "native")]
+mod storage {
+    use std::path::PathBuf;
+
+    fn path(file: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_local_dir()?
+                .join(env!("CARGO_PKG_NAME"))
+                .join(file),
+        )
+    }
+
+    pub fn read(file: &str) -> Option<String> {
+        std::fs::read_to_string(path(file)?).ok()
+    }
+
+    pub fn write(file: &str, text: &str) -> Result<(), String> {
+        let path = path(file).ok_or("no config directory")?;
+        // Create parent directories if necessary.
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(all(not(feature = "native"), target_family = "wasm"))]
+mod storage {
+    fn key(file: &str) -> String {
+        format!("{}/{file}", env!("CARGO_PKG_NAME"))
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(file: &str) -> Option<String> {
+        local_storage()?.get_item(&key(file)).ok()?
+    }
+
+    pub fn write(file: &str, text: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("no local storage")?
+            .set_item(&key(file), text)
+            .map_err(|e| format!("{e:?}"))
+    }
+}
+
+#[cfg(not(any(feature = "native", target_family = "wasm")))]
+mod storage {
+    pub fn read(_file: &str) -> Option<String> {
+        None
+    }
+
+    pub fn write(_file: &str, _text: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+    #[serde(default)]
+    struct TestPrefs {
+        volume: f32,
+        migrated_from: Option<u32>,
+    }
+
+    impl Prefs for TestPrefs {
+        const FILE: &'static str = "test.ron";
+        const VERSION: u32 = 2;
+
+        fn migrate(&mut self, version: u32) {
+            self.migrated_from = Some(version);
+        }
+
+        fn gather(_world: &World) -> Self {
+            default()
+        }
+
+        fn apply(self, _world: &mut World) {}
+
+        fn is_changed(_world: &World) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy() {
+        let prefs = parse_prefs::<TestPrefs>("(volume: 0.5)").unwrap();
+        assert_eq!(
+            prefs,
+            TestPrefs {
+                volume: 0.5,
+                migrated_from: Some(0),
+            },
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned() {
+        let prefs = parse_prefs::<TestPrefs>("(version: 2, settings: (volume: 0.5))").unwrap();
+        assert_eq!(
+            prefs,
+            TestPrefs {
+                volume: 0.5,
+                migrated_from: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_parse_old_version() {
+        let prefs = parse_prefs::<TestPrefs>("(version: 1, settings: (volume: 0.5))").unwrap();
+        assert_eq!(prefs.migrated_from, Some(1));
+    }
+
+    #[test]
+    fn test_parse_corrupt() {
+        for case in [
+            "(volume: 0.5",
+            "(version: 2, settings: (volume: \"loud\"))",
+            "(version: 2, settings: (volume: 0.5)",
+            "(version: \"2\", settings: (volume: 0.5))",
+        ] {
+            assert!(parse_prefs::<TestPrefs>(case).is_err(), "{case}");
+        }
+    }
+}